@@ -3,8 +3,13 @@ use yew::prelude::*;
 
 #[function_component]
 pub fn App() -> Html {
+    let history = use_order_history(OrderHistoryOptions {
+        shortcuts: true,
+        ..Default::default()
+    });
+
     html! {
-        <SortableList direction={ Direction::Row } contain=true>
+        <SortableList direction={ Direction::Row } contain=true history={ history }>
             <img src="https://i.imgur.com/u1d1yCc.png"/>
             <img src="https://i.imgur.com/VgiZEvT.png"/>
             <img src="https://i.imgur.com/JQIbF9R.png"/>
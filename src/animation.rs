@@ -0,0 +1,497 @@
+#[cfg(feature = "timers")]
+use yew::prelude::*;
+
+#[cfg(feature = "timers")]
+use crate::use_raf_loop;
+use crate::Color;
+
+/// A value that can be animated by [`Tween`] and [`Spring`].
+pub trait Animatable: Copy + PartialEq + 'static {
+    fn zero() -> Self;
+
+    fn add(self, other: Self) -> Self;
+
+    fn scale(self, factor: f32) -> Self;
+
+    /// Gets the length of the value, used to decide when an animation has settled.
+    fn magnitude(self) -> f32;
+
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        self.add(other.scale(-1.0))
+    }
+
+    #[inline]
+    fn lerp(self, to: Self, t: f32) -> Self {
+        self.add(to.sub(self).scale(t))
+    }
+}
+
+impl Animatable for f32 {
+    #[inline]
+    fn zero() -> Self {
+        0.0
+    }
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        self + other
+    }
+
+    #[inline]
+    fn scale(self, factor: f32) -> Self {
+        self * factor
+    }
+
+    #[inline]
+    fn magnitude(self) -> f32 {
+        self.abs()
+    }
+}
+
+impl Animatable for (f32, f32) {
+    #[inline]
+    fn zero() -> Self {
+        (0.0, 0.0)
+    }
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        (self.0 + other.0, self.1 + other.1)
+    }
+
+    #[inline]
+    fn scale(self, factor: f32) -> Self {
+        (self.0 * factor, self.1 * factor)
+    }
+
+    #[inline]
+    fn magnitude(self) -> f32 {
+        f32::hypot(self.0, self.1)
+    }
+}
+
+impl Animatable for Color {
+    #[inline]
+    fn zero() -> Self {
+        Self {
+            r: 0.0,
+            g: 0.0,
+            b: 0.0,
+            a: 0.0,
+        }
+    }
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        Self {
+            r: self.r + other.r,
+            g: self.g + other.g,
+            b: self.b + other.b,
+            a: self.a + other.a,
+        }
+    }
+
+    #[inline]
+    fn scale(self, factor: f32) -> Self {
+        Self {
+            r: self.r * factor,
+            g: self.g * factor,
+            b: self.b * factor,
+            a: self.a * factor,
+        }
+    }
+
+    #[inline]
+    fn magnitude(self) -> f32 {
+        // alpha is scaled to the same range as the color channels
+        let a = self.a * 255.0;
+        (self.r * self.r + self.g * self.g + self.b * self.b + a * a).sqrt()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    #[default]
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    /// Eases `t`, which goes from `0.0` to `1.0`.
+    #[inline]
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TweenConfig {
+    /// The duration of the tween in milliseconds.
+    pub duration: f32,
+    pub easing: Easing,
+}
+
+impl Default for TweenConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            duration: 200.0,
+            easing: Easing::default(),
+        }
+    }
+}
+
+/// Animates a value towards a target over a fixed duration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tween<T> {
+    from: T,
+    to: T,
+    elapsed: f32,
+    pub config: TweenConfig,
+}
+
+impl<T: Animatable> Tween<T> {
+    /// Creates a tween resting at `value`.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self::with_config(value, TweenConfig::default())
+    }
+
+    #[inline]
+    pub fn with_config(value: T, config: TweenConfig) -> Self {
+        Self {
+            from: value,
+            to: value,
+            elapsed: config.duration,
+            config,
+        }
+    }
+
+    /// Starts tweening from the current value to `target`.
+    #[inline]
+    pub fn set_target(&mut self, target: T) {
+        if target == self.to {
+            return;
+        }
+
+        self.from = self.value();
+        self.to = target;
+        self.elapsed = 0.0;
+    }
+
+    /// Jumps to `value`, stopping the tween.
+    #[inline]
+    pub fn set_value(&mut self, value: T) {
+        self.from = value;
+        self.to = value;
+        self.elapsed = self.config.duration;
+    }
+
+    #[inline]
+    pub fn target(&self) -> T {
+        self.to
+    }
+
+    #[inline]
+    pub fn value(&self) -> T {
+        if self.is_done() {
+            return self.to;
+        }
+
+        let t = self
+            .config
+            .easing
+            .apply(self.elapsed / self.config.duration);
+        self.from.lerp(self.to, t)
+    }
+
+    #[inline]
+    pub fn is_done(&self) -> bool {
+        self.elapsed >= self.config.duration
+    }
+
+    /// Advances the tween by `delta` milliseconds.
+    #[inline]
+    pub fn update(&mut self, delta: f32) -> T {
+        self.elapsed = f32::min(self.elapsed + delta, self.config.duration);
+        self.value()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpringConfig {
+    pub stiffness: f32,
+    pub damping: f32,
+    pub mass: f32,
+    /// The spring settles when both its speed and distance to the target are below this.
+    pub precision: f32,
+}
+
+impl Default for SpringConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            stiffness: 170.0,
+            damping: 26.0,
+            mass: 1.0,
+            precision: 0.01,
+        }
+    }
+}
+
+/// The longest step a spring is simulated with, in seconds, larger deltas are split.
+const MAX_SPRING_STEP: f32 = 1.0 / 120.0;
+
+/// Animates a value towards a target with a damped spring.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spring<T> {
+    value: T,
+    target: T,
+    velocity: T,
+    pub config: SpringConfig,
+}
+
+impl<T: Animatable> Spring<T> {
+    /// Creates a spring resting at `value`.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self::with_config(value, SpringConfig::default())
+    }
+
+    #[inline]
+    pub fn with_config(value: T, config: SpringConfig) -> Self {
+        Self {
+            value,
+            target: value,
+            velocity: T::zero(),
+            config,
+        }
+    }
+
+    /// Sets the velocity in units per second.
+    #[inline]
+    pub fn with_velocity(mut self, velocity: T) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    #[inline]
+    pub fn set_target(&mut self, target: T) {
+        self.target = target;
+    }
+
+    /// Jumps to `value`, stopping the spring.
+    #[inline]
+    pub fn set_value(&mut self, value: T) {
+        self.value = value;
+        self.target = value;
+        self.velocity = T::zero();
+    }
+
+    /// Sets the velocity in units per second.
+    #[inline]
+    pub fn set_velocity(&mut self, velocity: T) {
+        self.velocity = velocity;
+    }
+
+    #[inline]
+    pub fn target(&self) -> T {
+        self.target
+    }
+
+    #[inline]
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    #[inline]
+    pub fn velocity(&self) -> T {
+        self.velocity
+    }
+
+    #[inline]
+    pub fn is_settled(&self) -> bool {
+        self.value == self.target && self.velocity.magnitude() == 0.0
+    }
+
+    /// Advances the spring by `delta` milliseconds.
+    pub fn update(&mut self, delta: f32) -> T {
+        let mut remaining = delta / 1000.0;
+
+        while remaining > 0.0 && !self.is_settled() {
+            let dt = remaining.min(MAX_SPRING_STEP);
+            remaining -= dt;
+
+            let displacement = self.value.sub(self.target);
+            let spring = displacement.scale(-self.config.stiffness);
+            let damping = self.velocity.scale(-self.config.damping);
+            let acceleration = spring.add(damping).scale(1.0 / self.config.mass);
+
+            self.velocity = self.velocity.add(acceleration.scale(dt));
+            self.value = self.value.add(self.velocity.scale(dt));
+
+            let distance = self.value.sub(self.target).magnitude();
+            if distance < self.config.precision && self.velocity.magnitude() < self.config.precision
+            {
+                self.set_value(self.target);
+            }
+        }
+
+        self.value
+    }
+}
+
+/// Tweens towards `target` every frame, returning the current value.
+///
+/// Changing `target` starts a new tween from the current value.
+#[cfg(feature = "timers")]
+#[hook]
+pub fn use_tween<T>(target: T, config: TweenConfig) -> T
+where
+    T: Animatable,
+{
+    let tween = use_mut_ref(|| Tween::with_config(target, config));
+    let update = use_force_update();
+
+    tween.borrow_mut().config = config;
+    tween.borrow_mut().set_target(target);
+
+    let active = !tween.borrow().is_done();
+
+    {
+        let tween = tween.clone();
+
+        use_raf_loop(
+            move |_, delta| {
+                tween.borrow_mut().update(delta as f32);
+                update.force_update();
+            },
+            active,
+        );
+    }
+
+    let value = tween.borrow().value();
+    value
+}
+
+/// Springs towards `target` every frame, returning the current value.
+#[cfg(feature = "timers")]
+#[hook]
+pub fn use_spring<T>(target: T, config: SpringConfig) -> T
+where
+    T: Animatable,
+{
+    let spring = use_mut_ref(|| Spring::with_config(target, config));
+    let update = use_force_update();
+
+    spring.borrow_mut().config = config;
+    spring.borrow_mut().set_target(target);
+
+    let active = !spring.borrow().is_settled();
+
+    {
+        let spring = spring.clone();
+
+        use_raf_loop(
+            move |_, delta| {
+                spring.borrow_mut().update(delta as f32);
+                update.force_update();
+            },
+            active,
+        );
+    }
+
+    let value = spring.borrow().value();
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tween_reaches_target_after_duration() {
+        let mut tween = Tween::with_config(
+            0.0,
+            TweenConfig {
+                duration: 100.0,
+                easing: Easing::Linear,
+            },
+        );
+
+        tween.set_target(10.0);
+        assert!(!tween.is_done());
+
+        assert_eq!(tween.update(50.0), 5.0);
+        assert_eq!(tween.update(50.0), 10.0);
+        assert!(tween.is_done());
+    }
+
+    #[test]
+    fn tween_retargets_from_current_value() {
+        let mut tween = Tween::with_config(
+            (0.0, 0.0),
+            TweenConfig {
+                duration: 100.0,
+                easing: Easing::Linear,
+            },
+        );
+
+        tween.set_target((10.0, 20.0));
+        tween.update(50.0);
+
+        tween.set_target((0.0, 0.0));
+        assert_eq!(tween.value(), (5.0, 10.0));
+        assert_eq!(tween.update(50.0), (2.5, 5.0));
+    }
+
+    #[test]
+    fn easing_keeps_endpoints() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn spring_settles_on_target() {
+        let mut spring = Spring::new(Color::BLACK);
+        spring.set_target(Color::WHITE);
+
+        for _ in 0..120 {
+            spring.update(1000.0 / 60.0);
+        }
+
+        assert!(spring.is_settled());
+        assert_eq!(spring.value(), Color::WHITE);
+    }
+
+    #[test]
+    fn spring_uses_initial_velocity() {
+        let mut spring = Spring::new(0.0f32).with_velocity(100.0);
+
+        assert!(!spring.is_settled());
+        assert!(spring.update(10.0) > 0.0);
+    }
+}
@@ -0,0 +1,459 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+
+use wasm_bindgen::{closure::Closure, JsCast};
+
+use crate::function::Function;
+
+/// A source of time and timers.
+///
+/// [`Interval`](crate::Interval), [`Timeout`](crate::Timeout) and
+/// [`AnimationFrame`](crate::AnimationFrame) are scheduled on the current clock,
+/// which is a [`BrowserClock`] unless replaced with [`set_clock`].
+pub trait Clock {
+    /// Gets the current time in milliseconds.
+    fn now(&self) -> f64;
+
+    fn set_timeout(&self, ms: u32, f: Box<dyn FnOnce()>) -> i32;
+
+    fn clear_timeout(&self, id: i32);
+
+    fn set_interval(&self, ms: u32, f: Box<dyn FnMut()>) -> i32;
+
+    fn clear_interval(&self, id: i32);
+
+    /// Calls `f` with the current time before the next repaint.
+    fn request_animation_frame(&self, f: Box<dyn FnOnce(f64)>) -> i32;
+
+    fn cancel_animation_frame(&self, id: i32);
+}
+
+thread_local! {
+    static CLOCK: RefCell<Rc<dyn Clock>> = RefCell::new(Rc::new(BrowserClock));
+}
+
+/// Gets the current clock.
+#[inline]
+pub fn clock() -> Rc<dyn Clock> {
+    CLOCK.with(|clock| clock.borrow().clone())
+}
+
+/// Replaces the current clock, timers already scheduled are not moved.
+#[inline]
+pub fn set_clock(new_clock: Rc<dyn Clock>) {
+    CLOCK.with(|clock| *clock.borrow_mut() = new_clock);
+}
+
+type FrameClosure = Closure<dyn FnMut(f64)>;
+
+thread_local! {
+    /// The closures of the browser timers, kept until they're done or cleared.
+    static BROWSER_TIMERS: RefCell<HashMap<i32, Function>> = RefCell::default();
+
+    /// The closures of the requested animation frames, their ids are apart from the timers'.
+    static BROWSER_FRAMES: RefCell<HashMap<i32, FrameClosure>> = RefCell::default();
+}
+
+fn remove_browser_timer(id: i32) -> Option<Function> {
+    BROWSER_TIMERS.with(|timers| timers.borrow_mut().remove(&id))
+}
+
+fn remove_browser_frame(id: i32) -> Option<FrameClosure> {
+    BROWSER_FRAMES.with(|frames| frames.borrow_mut().remove(&id))
+}
+
+/// A [`Clock`] backed by the browser window.
+///
+/// The closures of the timers and animation frames are dropped once they're done or cleared.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BrowserClock;
+
+impl Clock for BrowserClock {
+    fn now(&self) -> f64 {
+        match web_sys::window().unwrap().performance() {
+            Some(performance) => performance.now(),
+            None => js_sys::Date::now(),
+        }
+    }
+
+    fn set_timeout(&self, ms: u32, f: Box<dyn FnOnce()>) -> i32 {
+        let timeout_id = Rc::new(Cell::new(0));
+
+        let function = Function::once({
+            let timeout_id = timeout_id.clone();
+
+            move || {
+                // the closure is running, wasm-bindgen frees it once it returns
+                let _function = remove_browser_timer(timeout_id.get());
+                f();
+            }
+        });
+
+        let id = web_sys::window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(function.as_ref(), ms as i32)
+            .expect("garlic failed to set timeout");
+
+        timeout_id.set(id);
+        BROWSER_TIMERS.with(|timers| timers.borrow_mut().insert(id, function));
+
+        id
+    }
+
+    fn clear_timeout(&self, id: i32) {
+        web_sys::window().unwrap().clear_timeout_with_handle(id);
+        remove_browser_timer(id);
+    }
+
+    fn set_interval(&self, ms: u32, f: Box<dyn FnMut()>) -> i32 {
+        let function = Function::new(f);
+
+        let id = web_sys::window()
+            .unwrap()
+            .set_interval_with_callback_and_timeout_and_arguments_0(function.as_ref(), ms as i32)
+            .expect("garlic failed to set interval");
+
+        BROWSER_TIMERS.with(|timers| timers.borrow_mut().insert(id, function));
+
+        id
+    }
+
+    fn clear_interval(&self, id: i32) {
+        web_sys::window().unwrap().clear_interval_with_handle(id);
+        remove_browser_timer(id);
+    }
+
+    fn request_animation_frame(&self, f: Box<dyn FnOnce(f64)>) -> i32 {
+        let frame_id = Rc::new(Cell::new(0));
+
+        let closure = Closure::once({
+            let frame_id = frame_id.clone();
+
+            move |timestamp| {
+                // the closure is running, wasm-bindgen frees it once it returns
+                let _closure = remove_browser_frame(frame_id.get());
+                f(timestamp);
+            }
+        });
+
+        let id = web_sys::window()
+            .unwrap()
+            .request_animation_frame(closure.as_ref().unchecked_ref())
+            .expect("garlic failed to request animation frame");
+
+        frame_id.set(id);
+        BROWSER_FRAMES.with(|frames| frames.borrow_mut().insert(id, closure));
+
+        id
+    }
+
+    fn cancel_animation_frame(&self, id: i32) {
+        let _ = web_sys::window().unwrap().cancel_animation_frame(id);
+        remove_browser_frame(id);
+    }
+}
+
+enum TimerFunction {
+    Once(Box<dyn FnOnce()>),
+    Repeat(Box<dyn FnMut()>),
+}
+
+type FrameFunction = Box<dyn FnOnce(f64)>;
+
+struct Timer {
+    id: i32,
+    at: f64,
+    interval: Option<u32>,
+    /// Taken while the timer is running.
+    function: Option<TimerFunction>,
+}
+
+#[derive(Default)]
+struct ManualClockState {
+    now: f64,
+    next_id: i32,
+    timers: Vec<Timer>,
+    frames: Vec<(i32, FrameFunction)>,
+}
+
+impl ManualClockState {
+    fn next_id(&mut self) -> i32 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    fn remove_timer(&mut self, id: i32) {
+        self.timers.retain(|timer| timer.id != id);
+    }
+}
+
+/// A [`Clock`] that only moves when advanced by hand.
+///
+/// Useful for testing timing logic outside of a browser.
+#[derive(Default)]
+pub struct ManualClock {
+    state: RefCell<ManualClockState>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advances the time by `ms` milliseconds, running every timer that is due in order.
+    pub fn advance(&self, ms: f64) {
+        let target = self.state.borrow().now + ms;
+
+        loop {
+            // timers are run without borrowing the state, since they may schedule new timers
+            let (id, function) = {
+                let mut state = self.state.borrow_mut();
+
+                let next = state
+                    .timers
+                    .iter_mut()
+                    .filter(|timer| timer.function.is_some() && timer.at <= target)
+                    .min_by(|a, b| a.at.total_cmp(&b.at).then(a.id.cmp(&b.id)));
+
+                let Some(timer) = next else {
+                    break;
+                };
+
+                let at = timer.at;
+                let id = timer.id;
+                let function = timer.function.take().unwrap();
+
+                state.now = at;
+
+                (id, function)
+            };
+
+            match function {
+                TimerFunction::Once(function) => {
+                    self.state.borrow_mut().remove_timer(id);
+                    function();
+                }
+                TimerFunction::Repeat(mut function) => {
+                    function();
+
+                    let mut state = self.state.borrow_mut();
+
+                    // the interval may have been cleared while running
+                    if let Some(timer) = state.timers.iter_mut().find(|timer| timer.id == id) {
+                        timer.at += timer.interval.unwrap_or_default().max(1) as f64;
+                        timer.function = Some(TimerFunction::Repeat(function));
+                    }
+                }
+            }
+        }
+
+        self.state.borrow_mut().now = target;
+    }
+
+    /// Advances the time by `ms` milliseconds and runs the pending animation frames.
+    ///
+    /// Frames requested while running are run on the next call.
+    pub fn frame(&self, ms: f64) {
+        self.advance(ms);
+
+        let (now, frames) = {
+            let mut state = self.state.borrow_mut();
+            (state.now, std::mem::take(&mut state.frames))
+        };
+
+        for (_, frame) in frames {
+            frame(now);
+        }
+    }
+
+    /// Gets the number of pending timers and animation frames.
+    pub fn pending(&self) -> usize {
+        let state = self.state.borrow();
+        state.timers.len() + state.frames.len()
+    }
+
+    fn add_timer(&self, ms: u32, interval: Option<u32>, function: TimerFunction) -> i32 {
+        let mut state = self.state.borrow_mut();
+        let id = state.next_id();
+        let at = state.now + ms as f64;
+
+        state.timers.push(Timer {
+            id,
+            at,
+            interval,
+            function: Some(function),
+        });
+
+        id
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> f64 {
+        self.state.borrow().now
+    }
+
+    fn set_timeout(&self, ms: u32, f: Box<dyn FnOnce()>) -> i32 {
+        self.add_timer(ms, None, TimerFunction::Once(f))
+    }
+
+    fn clear_timeout(&self, id: i32) {
+        self.state.borrow_mut().remove_timer(id);
+    }
+
+    fn set_interval(&self, ms: u32, f: Box<dyn FnMut()>) -> i32 {
+        self.add_timer(ms.max(1), Some(ms), TimerFunction::Repeat(f))
+    }
+
+    fn clear_interval(&self, id: i32) {
+        self.state.borrow_mut().remove_timer(id);
+    }
+
+    fn request_animation_frame(&self, f: Box<dyn FnOnce(f64)>) -> i32 {
+        let mut state = self.state.borrow_mut();
+        let id = state.next_id();
+        state.frames.push((id, f));
+        id
+    }
+
+    fn cancel_animation_frame(&self, id: i32) {
+        let mut state = self.state.borrow_mut();
+        state.frames.retain(|(frame, _)| *frame != id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use yew::Callback;
+
+    use super::*;
+    use crate::{AnimationFrame, Debounce, Interval, Throttle, Timeout};
+
+    fn manual_clock() -> Rc<ManualClock> {
+        let clock = Rc::new(ManualClock::new());
+        set_clock(clock.clone());
+        clock
+    }
+
+    fn counter() -> (Rc<Cell<u32>>, Callback<u32>) {
+        let count = Rc::new(Cell::new(0));
+        let callback = {
+            let count = count.clone();
+            Callback::from(move |n| count.set(count.get() + n))
+        };
+
+        (count, callback)
+    }
+
+    #[test]
+    fn timeout_fires_once() {
+        let clock = manual_clock();
+        let (count, callback) = counter();
+
+        let _timeout = Timeout::new(100, move || callback.emit(1));
+
+        clock.advance(99.0);
+        assert_eq!(count.get(), 0);
+
+        clock.advance(1.0);
+        assert_eq!(count.get(), 1);
+
+        clock.advance(1000.0);
+        assert_eq!(count.get(), 1);
+    }
+
+    #[test]
+    fn dropped_timeout_is_cancelled() {
+        let clock = manual_clock();
+        let (count, callback) = counter();
+
+        drop(Timeout::new(100, move || callback.emit(1)));
+
+        clock.advance(100.0);
+        assert_eq!(count.get(), 0);
+        assert_eq!(clock.pending(), 0);
+    }
+
+    #[test]
+    fn interval_repeats_until_dropped() {
+        let clock = manual_clock();
+        let (count, callback) = counter();
+
+        let interval = Interval::new(10, move || callback.emit(1));
+
+        clock.advance(35.0);
+        assert_eq!(count.get(), 3);
+
+        drop(interval);
+
+        clock.advance(100.0);
+        assert_eq!(count.get(), 3);
+    }
+
+    #[test]
+    fn animation_frame_receives_time() {
+        let manual = manual_clock();
+        let time = Rc::new(Cell::new(0.0));
+
+        let frame_time = time.clone();
+        let _frame = AnimationFrame::new(move || frame_time.set(clock().now())).request();
+
+        manual.frame(16.0);
+        assert_eq!(time.get(), 16.0);
+    }
+
+    #[test]
+    fn dropped_animation_frame_is_cancelled() {
+        let manual = manual_clock();
+        let (count, callback) = counter();
+
+        drop(AnimationFrame::new(move || callback.emit(1)).request());
+
+        manual.frame(16.0);
+        assert_eq!(count.get(), 0);
+        assert_eq!(manual.pending(), 0);
+    }
+
+    #[test]
+    fn debounce_emits_last_value() {
+        let clock = manual_clock();
+        let (count, callback) = counter();
+
+        let debounce = Debounce::new(50, callback);
+
+        debounce.call(1);
+        clock.advance(40.0);
+        debounce.call(2);
+        clock.advance(40.0);
+        assert_eq!(count.get(), 0);
+        assert!(debounce.is_pending());
+
+        clock.advance(10.0);
+        assert_eq!(count.get(), 2);
+        assert!(!debounce.is_pending());
+    }
+
+    #[test]
+    fn throttle_emits_first_and_last_value() {
+        let clock = manual_clock();
+        let (count, callback) = counter();
+
+        let throttle = Throttle::new(50, callback);
+
+        throttle.call(1);
+        throttle.call(10);
+        throttle.call(100);
+        assert_eq!(count.get(), 1);
+
+        clock.advance(50.0);
+        assert_eq!(count.get(), 101);
+
+        clock.advance(50.0);
+        assert!(!throttle.is_throttled());
+    }
+}
@@ -0,0 +1,46 @@
+use std::fmt::{self, Display};
+
+/// An sRGB color, with channels from `0.0` to `255.0` and alpha from `0.0` to `1.0`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Color {
+    pub const TRANSPARENT: Self = Self::rgba(0, 0, 0, 0.0);
+    pub const BLACK: Self = Self::rgb(0, 0, 0);
+    pub const WHITE: Self = Self::rgb(255, 255, 255);
+
+    #[inline]
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::rgba(r, g, b, 1.0)
+    }
+
+    #[inline]
+    pub const fn rgba(r: u8, g: u8, b: u8, a: f32) -> Self {
+        Self {
+            r: r as f32,
+            g: g as f32,
+            b: b as f32,
+            a,
+        }
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let channel = |c: f32| c.round().clamp(0.0, 255.0) as u8;
+
+        write!(
+            f,
+            "rgba({}, {}, {}, {})",
+            channel(self.r),
+            channel(self.g),
+            channel(self.b),
+            self.a.clamp(0.0, 1.0),
+        )
+    }
+}
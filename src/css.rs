@@ -0,0 +1,318 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Add, Div, Mul, Sub},
+};
+
+/// A CSS length.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Length {
+    Px(f32),
+    Percent(f32),
+    Em(f32),
+    Rem(f32),
+    Vw(f32),
+    Vh(f32),
+    Auto,
+    Calc(Box<Calc>),
+}
+
+impl Length {
+    #[inline]
+    pub const fn px(value: f32) -> Self {
+        Self::Px(value)
+    }
+
+    #[inline]
+    pub const fn percent(value: f32) -> Self {
+        Self::Percent(value)
+    }
+
+    #[inline]
+    pub const fn em(value: f32) -> Self {
+        Self::Em(value)
+    }
+
+    #[inline]
+    pub const fn rem(value: f32) -> Self {
+        Self::Rem(value)
+    }
+
+    #[inline]
+    pub const fn vw(value: f32) -> Self {
+        Self::Vw(value)
+    }
+
+    #[inline]
+    pub const fn vh(value: f32) -> Self {
+        Self::Vh(value)
+    }
+
+    #[inline]
+    pub fn calc(calc: Calc) -> Self {
+        Self::Calc(Box::new(calc))
+    }
+
+    fn fmt_inner(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            // nested calculations don't need another `calc`
+            Length::Calc(calc) => write!(f, "({})", calc),
+            length => write!(f, "{}", length),
+        }
+    }
+}
+
+impl Default for Length {
+    #[inline]
+    fn default() -> Self {
+        Self::Px(0.0)
+    }
+}
+
+impl Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Length::Px(value) => write!(f, "{}px", value),
+            Length::Percent(value) => write!(f, "{}%", value),
+            Length::Em(value) => write!(f, "{}em", value),
+            Length::Rem(value) => write!(f, "{}rem", value),
+            Length::Vw(value) => write!(f, "{}vw", value),
+            Length::Vh(value) => write!(f, "{}vh", value),
+            Length::Auto => write!(f, "auto"),
+            Length::Calc(calc) => write!(f, "calc({})", calc),
+        }
+    }
+}
+
+/// A `calc()` expression, usually built with arithmetic on [`Length`]s.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Calc {
+    Add(Length, Length),
+    Sub(Length, Length),
+    Mul(Length, f32),
+    Div(Length, f32),
+}
+
+impl Display for Calc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Calc::Add(a, b) => {
+                a.fmt_inner(f)?;
+                write!(f, " + ")?;
+                b.fmt_inner(f)
+            }
+            Calc::Sub(a, b) => {
+                a.fmt_inner(f)?;
+                write!(f, " - ")?;
+                b.fmt_inner(f)
+            }
+            Calc::Mul(a, b) => {
+                a.fmt_inner(f)?;
+                write!(f, " * {}", b)
+            }
+            Calc::Div(a, b) => {
+                a.fmt_inner(f)?;
+                write!(f, " / {}", b)
+            }
+        }
+    }
+}
+
+impl Add for Length {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::calc(Calc::Add(self, rhs))
+    }
+}
+
+impl Sub for Length {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::calc(Calc::Sub(self, rhs))
+    }
+}
+
+impl Mul<f32> for Length {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self {
+        Self::calc(Calc::Mul(self, rhs))
+    }
+}
+
+impl Div<f32> for Length {
+    type Output = Self;
+
+    fn div(self, rhs: f32) -> Self {
+        Self::calc(Calc::Div(self, rhs))
+    }
+}
+
+/// A single CSS transform function.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TransformFunction {
+    Translate(Length, Length),
+    Translate3d(Length, Length, Length),
+    Scale(f32, f32),
+    /// A rotation in degrees.
+    Rotate(f32),
+}
+
+impl TransformFunction {
+    /// Translates by `x` and `y` pixels, with `translate3d` to stay on the compositor.
+    #[inline]
+    pub fn translate_px(x: f32, y: f32) -> Self {
+        Self::Translate3d(Length::px(x), Length::px(y), Length::px(0.0))
+    }
+}
+
+impl Display for TransformFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransformFunction::Translate(x, y) => write!(f, "translate({}, {})", x, y),
+            TransformFunction::Translate3d(x, y, z) => {
+                write!(f, "translate3d({}, {}, {})", x, y, z)
+            }
+            TransformFunction::Scale(x, y) => write!(f, "scale({}, {})", x, y),
+            TransformFunction::Rotate(degrees) => write!(f, "rotate({}deg)", degrees),
+        }
+    }
+}
+
+/// A list of CSS transform functions, applied in order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Transform {
+    pub functions: Vec<TransformFunction>,
+}
+
+impl Transform {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            functions: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn with(mut self, function: TransformFunction) -> Self {
+        self.functions.push(function);
+        self
+    }
+
+    #[inline]
+    pub fn translate(self, x: Length, y: Length) -> Self {
+        self.with(TransformFunction::Translate(x, y))
+    }
+
+    #[inline]
+    pub fn translate3d(self, x: Length, y: Length, z: Length) -> Self {
+        self.with(TransformFunction::Translate3d(x, y, z))
+    }
+
+    #[inline]
+    pub fn translate_px(self, x: f32, y: f32) -> Self {
+        self.with(TransformFunction::translate_px(x, y))
+    }
+
+    #[inline]
+    pub fn scale(self, x: f32, y: f32) -> Self {
+        self.with(TransformFunction::Scale(x, y))
+    }
+
+    #[inline]
+    pub fn rotate(self, degrees: f32) -> Self {
+        self.with(TransformFunction::Rotate(degrees))
+    }
+}
+
+impl From<TransformFunction> for Transform {
+    #[inline]
+    fn from(function: TransformFunction) -> Self {
+        Self::new().with(function)
+    }
+}
+
+impl Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.functions.is_empty() {
+            return write!(f, "none");
+        }
+
+        for (i, function) in self.functions.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+
+            write!(f, "{}", function)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, Style};
+
+    #[test]
+    fn length_units() {
+        assert_eq!(Length::px(10.0).to_string(), "10px");
+        assert_eq!(Length::px(-0.5).to_string(), "-0.5px");
+        assert_eq!(Length::percent(50.0).to_string(), "50%");
+        assert_eq!(Length::em(1.5).to_string(), "1.5em");
+        assert_eq!(Length::rem(2.0).to_string(), "2rem");
+        assert_eq!(Length::vw(100.0).to_string(), "100vw");
+        assert_eq!(Length::vh(100.0).to_string(), "100vh");
+        assert_eq!(Length::Auto.to_string(), "auto");
+    }
+
+    #[test]
+    fn length_calc() {
+        let length = Length::percent(100.0) - Length::px(20.0);
+        assert_eq!(length.to_string(), "calc(100% - 20px)");
+
+        let length = (Length::vw(100.0) - Length::rem(2.0)) / 2.0 + Length::px(4.0);
+        assert_eq!(length.to_string(), "calc(((100vw - 2rem) / 2) + 4px)");
+    }
+
+    #[test]
+    fn transform_list() {
+        assert_eq!(Transform::new().to_string(), "none");
+
+        let transform = Transform::new()
+            .translate_px(10.0, 20.0)
+            .scale(2.0, 2.0)
+            .rotate(45.0);
+
+        assert_eq!(
+            transform.to_string(),
+            "translate3d(10px, 20px, 0px) scale(2, 2) rotate(45deg)"
+        );
+    }
+
+    #[test]
+    fn style_setters() {
+        let mut style = Style::new();
+
+        style
+            .left(Length::px(10.0))
+            .width(Length::percent(100.0) - Length::px(4.0))
+            .z_index(10)
+            .opacity(0.5)
+            .background_color(Color::rgba(255, 0, 0, 0.5))
+            .transform(Transform::new().translate_px(1.0, 2.0))
+            .push_transform(TransformFunction::Rotate(90.0));
+
+        assert_eq!(
+            style.to_string(),
+            "left:10px;\
+             width:calc(100% - 4px);\
+             z-index:10;\
+             opacity:0.5;\
+             background-color:rgba(255, 0, 0, 0.5);\
+             transform:translate3d(1px, 2px, 0px) rotate(90deg);"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use std::{cell::RefCell, rc::Rc};
+
+use yew::prelude::*;
+
+use crate::Timeout;
+
+/// Delays emitting a callback until it hasn't been called for `ms` milliseconds.
+///
+/// Only the last value is emitted. A pending call is cancelled when dropped.
+pub struct Debounce<T> {
+    ms: u32,
+    callback: Callback<T>,
+    timeout: Rc<RefCell<Option<Timeout>>>,
+}
+
+impl<T: 'static> Debounce<T> {
+    pub fn new(ms: u32, callback: Callback<T>) -> Self {
+        Self {
+            ms,
+            callback,
+            timeout: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    /// Emits `value` after `ms` milliseconds, cancelling any pending call.
+    pub fn call(&self, value: T) {
+        let callback = self.callback.clone();
+        let pending = Rc::downgrade(&self.timeout);
+
+        let timeout = Timeout::new(self.ms, move || {
+            if let Some(pending) = pending.upgrade() {
+                pending.borrow_mut().take();
+            }
+
+            callback.emit(value);
+        });
+
+        self.timeout.borrow_mut().replace(timeout);
+    }
+
+    /// Cancels the pending call if any.
+    pub fn cancel(&self) {
+        self.timeout.borrow_mut().take();
+    }
+
+    /// Returns true if a call is pending.
+    pub fn is_pending(&self) -> bool {
+        self.timeout.borrow().is_some()
+    }
+}
+
+impl<T> Drop for Debounce<T> {
+    fn drop(&mut self) {
+        self.timeout.borrow_mut().take();
+    }
+}
+
+/// Returns a callback that emits `callback` once it hasn't been called for `ms` milliseconds.
+///
+/// The latest `callback` is always emitted. A pending call is cancelled on unmount.
+// the bound is duplicated by `#[hook]`
+#[allow(clippy::multiple_bound_locations)]
+#[hook]
+pub fn use_debounce<T: 'static>(callback: Callback<T>, ms: u32) -> Callback<T> {
+    let latest = use_mut_ref(|| callback.clone());
+    *latest.borrow_mut() = callback;
+
+    let debounce = use_memo(
+        |ms| {
+            let callback = Callback::from(move |value| {
+                let callback = latest.borrow().clone();
+                callback.emit(value);
+            });
+
+            let debounce = Rc::new(Debounce::new(*ms, callback));
+
+            let call = {
+                let debounce = debounce.clone();
+                Callback::from(move |value| debounce.call(value))
+            };
+
+            (debounce, call)
+        },
+        ms,
+    );
+
+    {
+        let debounce = debounce.0.clone();
+        use_effect_with_deps(move |_| move || debounce.cancel(), ms);
+    }
+
+    debounce.1.clone()
+}
@@ -1,42 +1,40 @@
-use web_sys::HtmlElement;
+use web_sys::Element;
 use yew::prelude::*;
 
-use crate::Interval;
+use crate::{ResizeBox, ResizeObservation, Size};
 
 #[derive(Properties, PartialEq)]
 pub struct DetectResizeProps {
     #[prop_or_default]
     pub node_ref: NodeRef,
+    /// The box of the element to observe.
     #[prop_or_default]
-    pub onresize: Callback<()>,
+    pub resize_box: ResizeBox,
+    /// Called with the new size of the element when it's resized.
+    #[prop_or_default]
+    pub onresize: Callback<Size>,
 }
 
 #[function_component]
 pub fn DetectResize(props: &DetectResizeProps) -> Html {
-    let size = use_mut_ref(|| (0, 0));
-    let interval = use_state(|| None);
+    // the callback is kept separately so that it changing doesn't restart the observation
+    let onresize = use_mut_ref(|| props.onresize.clone());
+    *onresize.borrow_mut() = props.onresize.clone();
 
     use_effect_with_deps(
-        move |(node_ref, onresize)| {
-            let node_ref = node_ref.clone();
-            let onresize = onresize.clone();
-            let size = size.clone();
-
-            let frame = move || {
-                if let Some(element) = node_ref.cast::<HtmlElement>() {
-                    let rect = element.get_bounding_client_rect();
+        move |(node_ref, resize_box)| {
+            let observation = node_ref.cast::<Element>().map(|element| {
+                let callback = Callback::from(move |size| {
+                    let onresize = onresize.borrow().clone();
+                    onresize.emit(size);
+                });
 
-                    let (width, height) = (rect.width() as i32, rect.height() as i32);
-                    if *size.borrow() != (width, height) {
-                        *size.borrow_mut() = (width, height);
-                        onresize.emit(());
-                    }
-                }
-            };
+                ResizeObservation::new(&element, *resize_box, callback)
+            });
 
-            interval.set(Some(Interval::new(frame)));
+            move || drop(observation)
         },
-        (props.node_ref.clone(), props.onresize.clone()),
+        (props.node_ref.clone(), props.resize_box),
     );
 
     Html::default()
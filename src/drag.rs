@@ -1,7 +1,7 @@
 use web_sys::HtmlElement;
 use yew::prelude::*;
 
-use crate::{Direction, Style};
+use crate::{use_stylesheet, Direction, EventListener, Style, TransformFunction};
 
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct DragPosition {
@@ -67,37 +67,28 @@ impl DragContext {
     }
 }
 
-#[doc(hidden)]
-#[derive(Properties, PartialEq)]
-pub struct DragAreaProps {
-    #[prop_or_default]
-    pub class: Classes,
-    #[prop_or_default]
-    pub style: String,
-    #[prop_or_default]
-    pub children: Children,
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DragAreaOptions {
     /// The direction of the drag area.
     ///
     /// If set dragging will only be possible in the specified direction.
     pub direction: Option<Direction>,
     /// If true, dragging outside of the drag area will be prevented.
-    #[prop_or_default]
     pub contain: bool,
-    #[prop_or_default]
-    pub node_ref: NodeRef,
     /// Called when the drag starts.
-    #[prop_or_default]
     pub ondrag: Callback<DragEvent>,
     /// Called when the drag moves.
-    #[prop_or_default]
     pub onmove: Callback<DragEvent>,
     /// Called when the drag ends.
-    #[prop_or_default]
     pub ondrop: Callback<DragEvent>,
 }
 
-#[function_component]
-pub fn DragArea(props: &DragAreaProps) -> Html {
+/// Makes the element referenced by `node_ref` a drag area.
+///
+/// The returned [`DragContext`] and [`DragPosition`] must be provided to the children
+/// of the element with [`ContextProvider`]s, for [`use_draggable`] and [`use_droppable`] to work.
+#[hook]
+pub fn use_drag_area(node_ref: NodeRef, options: DragAreaOptions) -> (DragContext, DragPosition) {
     let dragged = use_state(NodeRef::default);
     let is_dragging = use_state(|| false);
     let position = use_state(DragPosition::default);
@@ -113,7 +104,7 @@ pub fn DragArea(props: &DragAreaProps) -> Html {
             dragged.clone(),
             is_dragging.clone(),
             position.clone(),
-            props.ondrag.clone(),
+            options.ondrag.clone(),
         ),
     );
 
@@ -131,12 +122,12 @@ pub fn DragArea(props: &DragAreaProps) -> Html {
             onmove.emit(event);
         },
         (
-            props.node_ref.clone(),
+            node_ref,
             dragged.clone(),
             position.clone(),
-            props.onmove.clone(),
-            props.contain,
-            props.direction,
+            options.onmove.clone(),
+            options.contain,
+            options.direction,
         ),
     );
 
@@ -156,7 +147,7 @@ pub fn DragArea(props: &DragAreaProps) -> Html {
                     ondrop.emit(event);
                 }
             },
-            (dragged.clone(), is_dragging.clone(), props.ondrop.clone()),
+            (dragged.clone(), is_dragging.clone(), options.ondrop),
         )
     };
 
@@ -168,6 +159,51 @@ pub fn DragArea(props: &DragAreaProps) -> Html {
         ondrop,
     };
 
+    (context, *position)
+}
+
+#[doc(hidden)]
+#[derive(Properties, PartialEq)]
+pub struct DragAreaProps {
+    #[prop_or_default]
+    pub class: Classes,
+    #[prop_or_default]
+    pub style: String,
+    #[prop_or_default]
+    pub children: Children,
+    /// The direction of the drag area.
+    ///
+    /// If set dragging will only be possible in the specified direction.
+    pub direction: Option<Direction>,
+    /// If true, dragging outside of the drag area will be prevented.
+    #[prop_or_default]
+    pub contain: bool,
+    #[prop_or_default]
+    pub node_ref: NodeRef,
+    /// Called when the drag starts.
+    #[prop_or_default]
+    pub ondrag: Callback<DragEvent>,
+    /// Called when the drag moves.
+    #[prop_or_default]
+    pub onmove: Callback<DragEvent>,
+    /// Called when the drag ends.
+    #[prop_or_default]
+    pub ondrop: Callback<DragEvent>,
+}
+
+#[function_component]
+pub fn DragArea(props: &DragAreaProps) -> Html {
+    let (context, position) = use_drag_area(
+        props.node_ref.clone(),
+        DragAreaOptions {
+            direction: props.direction,
+            contain: props.contain,
+            ondrag: props.ondrag.clone(),
+            onmove: props.onmove.clone(),
+            ondrop: props.ondrop.clone(),
+        },
+    );
+
     let mut style = Style::new()
         .with("width", "fit-content")
         .with("height", "fit-content");
@@ -181,7 +217,7 @@ pub fn DragArea(props: &DragAreaProps) -> Html {
             ref={ props.node_ref.clone() }
         >
             <ContextProvider<DragContext> { context }>
-                <ContextProvider<DragPosition> context={ *position }>
+                <ContextProvider<DragPosition> context={ position }>
                     { for props.children.iter() }
                 </ContextProvider<DragPosition>>
             </ContextProvider<DragContext>>
@@ -189,6 +225,151 @@ pub fn DragArea(props: &DragAreaProps) -> Html {
     }
 }
 
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DraggableOptions {
+    /// Called when the element is dragged.
+    pub ondrag: Callback<DragEvent>,
+    /// Called when the element is moved while being dragging.
+    pub onmove: Callback<DragEvent>,
+    /// Called when the element is dropped.
+    pub ondrop: Callback<DragEvent>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DraggableState {
+    /// True while the element is being dragged.
+    pub is_dragging: bool,
+    /// How far the element has been dragged since the drag started.
+    ///
+    /// This should be applied as a translation on top of any transform the element
+    /// already has, see [`DraggableState::translate`].
+    pub transform: DragPosition,
+}
+
+impl DraggableState {
+    /// Returns the `translate3d` transform function for [`DraggableState::transform`].
+    #[inline]
+    pub fn translate(&self) -> TransformFunction {
+        TransformFunction::translate_px(self.transform.x as f32, self.transform.y as f32)
+    }
+}
+
+/// Makes the element referenced by `node_ref` draggable.
+///
+/// Must be used inside a [`DragArea`]. No styles are applied to the element,
+/// the returned [`DraggableState`] should be used to position it while it's dragged.
+#[hook]
+pub fn use_draggable(node_ref: NodeRef, options: DraggableOptions) -> DraggableState {
+    let context = use_context::<DragContext>().expect("use_draggable must be used inside DragArea");
+    let position =
+        use_context::<DragPosition>().expect("use_draggable must be used inside DragArea");
+    let start = use_state(DragPosition::default);
+
+    // the context changes on every move while dragging, so the listeners read the latest
+    // values instead of being attached again
+    let latest = use_mut_ref(|| (context.clone(), start.clone(), options.clone()));
+    *latest.borrow_mut() = (context.clone(), start.clone(), options);
+
+    use_effect_with_deps(
+        move |node_ref| {
+            let listeners = node_ref.cast::<HtmlElement>().map(|element| {
+                let onpointerdown = {
+                    let node_ref = node_ref.clone();
+                    let latest = latest.clone();
+
+                    EventListener::new(&element, "pointerdown", move |event: PointerEvent| {
+                        let (context, start, options) = latest.borrow().clone();
+
+                        if event.button() == 0 {
+                            event.prevent_default();
+                            event.stop_propagation();
+
+                            let drag_start = DragEvent {
+                                position: DragPosition::new(&event),
+                                node_ref: node_ref.clone(),
+                            };
+
+                            context.ondrag.emit(drag_start.clone());
+                            options.ondrag.emit(drag_start.clone());
+
+                            let element = node_ref.cast::<HtmlElement>().unwrap();
+                            element.set_pointer_capture(event.pointer_id()).unwrap();
+
+                            start.set(drag_start.position);
+                        }
+                    })
+                };
+
+                let onpointermove = {
+                    let node_ref = node_ref.clone();
+                    let latest = latest.clone();
+
+                    EventListener::new(&element, "pointermove", move |event: PointerEvent| {
+                        let (context, _, options) = latest.borrow().clone();
+
+                        if context.is_dragged(&node_ref) {
+                            event.prevent_default();
+                            event.stop_propagation();
+
+                            let position = DragPosition::new(&event);
+
+                            let event = DragEvent {
+                                position,
+                                node_ref: node_ref.clone(),
+                            };
+
+                            context.onmove.emit(position);
+                            options.onmove.emit(event);
+                        }
+                    })
+                };
+
+                let onpointerup = {
+                    let node_ref = node_ref.clone();
+
+                    EventListener::new(&element, "pointerup", move |event: PointerEvent| {
+                        let (context, _, options) = latest.borrow().clone();
+
+                        if context.is_dragged(&node_ref) {
+                            event.prevent_default();
+                            event.stop_propagation();
+
+                            let drag_event = DragEvent {
+                                position: DragPosition::new(&event),
+                                node_ref: node_ref.clone(),
+                            };
+
+                            context.ondrop.emit(node_ref.clone());
+                            options.ondrop.emit(drag_event);
+                        }
+                    })
+                };
+
+                [onpointerdown, onpointermove, onpointerup]
+            });
+
+            move || drop(listeners)
+        },
+        node_ref.clone(),
+    );
+
+    let is_dragging = context.is_dragged(&node_ref);
+
+    let transform = if is_dragging {
+        DragPosition {
+            x: position.x - start.x,
+            y: position.y - start.y,
+        }
+    } else {
+        DragPosition::default()
+    };
+
+    DraggableState {
+        is_dragging,
+        transform,
+    }
+}
+
 #[derive(Properties, PartialEq)]
 pub struct DraggableProps {
     #[prop_or_default]
@@ -199,7 +380,9 @@ pub struct DraggableProps {
     pub children: Children,
     #[prop_or_default]
     pub node_ref: NodeRef,
+    /// Does nothing, the dragged element always keeps its space.
     #[prop_or_default]
+    #[deprecated(note = "the dragged element always keeps its space, it's moved with a transform")]
     pub keep_space: bool,
     /// Called when the element is dragged.
     #[prop_or_default]
@@ -214,110 +397,35 @@ pub struct DraggableProps {
 
 #[function_component]
 pub fn Draggable(props: &DraggableProps) -> Html {
-    let context = use_context::<DragContext>().expect("Draggable must be used inside DragArea");
-    let position = use_context::<DragPosition>().expect("Draggable must be used inside DragArea");
-    let offset = use_state(DragPosition::default);
-
-    let onpointerdown = use_callback(
-        |event: PointerEvent, (node_ref, context, offset, ondrag)| {
-            if event.button() == 0 {
-                event.prevent_default();
-                event.stop_propagation();
-
-                let drag_start = DragEvent {
-                    position: DragPosition::new(&event),
-                    node_ref: node_ref.clone(),
-                };
-
-                context.ondrag.emit(drag_start.clone());
-                ondrag.emit(drag_start.clone());
-
-                let element = node_ref.cast::<HtmlElement>().unwrap();
-                element.set_pointer_capture(event.pointer_id()).unwrap();
-
-                let x = element.offset_left();
-                let y = element.offset_top();
-
-                offset.set(DragPosition {
-                    x: drag_start.position.x - x,
-                    y: drag_start.position.y - y,
-                });
-            }
+    use_stylesheet();
+
+    let state = use_draggable(
+        props.node_ref.clone(),
+        DraggableOptions {
+            ondrag: props.ondrag.clone(),
+            onmove: props.onmove.clone(),
+            ondrop: props.ondrop.clone(),
         },
-        (
-            props.node_ref.clone(),
-            context.clone(),
-            offset.clone(),
-            props.ondrag.clone(),
-        ),
-    );
-
-    let onpointermove = use_callback(
-        |event: PointerEvent, (node_ref, context, onmove)| {
-            if context.is_dragged(&node_ref) {
-                event.prevent_default();
-                event.stop_propagation();
-
-                let position = DragPosition::new(&event);
-
-                let event = DragEvent {
-                    position,
-                    node_ref: node_ref.clone(),
-                };
-
-                context.onmove.emit(position);
-                onmove.emit(event);
-            }
-        },
-        (
-            props.node_ref.clone(),
-            context.clone(),
-            props.onmove.clone(),
-        ),
-    );
-
-    let onpointerup = use_callback(
-        |event: PointerEvent, (node_ref, context, ondrop)| {
-            if context.is_dragged(&node_ref) {
-                event.prevent_default();
-                event.stop_propagation();
-
-                let drag_event = DragEvent {
-                    position: DragPosition::new(&event),
-                    node_ref: node_ref.clone(),
-                };
-
-                context.ondrop.emit(node_ref.clone());
-                ondrop.emit(drag_event);
-            }
-        },
-        (
-            props.node_ref.clone(),
-            context.clone(),
-            props.ondrop.clone(),
-        ),
     );
 
     let mut style = Style::new();
 
     style.parse(&props.style);
 
-    if context.is_dragged(&props.node_ref) {
-        style.set("position", "absolute");
-        style.set("left", format!("{}px", position.x - offset.x));
-        style.set("top", format!("{}px", position.y - offset.y));
-        style.set("z-index", "1000");
-        style.set("cursor", "grabbing");
-    } else {
-        style.set("cursor", "grab");
+    if state.is_dragging {
+        // the drag translation is applied on top of any transform the element already has,
+        // this way the element stays in the layout and moves stay on the compositor
+        style.push_transform(state.translate());
+        style.set("will-change", "transform");
     }
 
     html! {
         <div
-            class={ classes!("garlic-draggable", props.class.clone()) }
-            onpointerdown={ onpointerdown }
-            onpointermove={ onpointermove }
-            onpointerup={ onpointerup }
+            class={ classes!(
+                "garlic-draggable",
+                state.is_dragging.then_some("garlic-dragging"),
+                props.class.clone(),
+            ) }
             style={ style }
             ref={props.node_ref.clone()}
         >
@@ -332,29 +440,29 @@ pub struct DragEvent {
     pub node_ref: NodeRef,
 }
 
-#[derive(Clone, Properties, PartialEq)]
-pub struct DroppableProps {
-    #[prop_or_default]
-    pub class: Classes,
-    #[prop_or_default]
-    pub style: String,
-    #[prop_or_default]
-    pub children: Children,
-    #[prop_or_default]
-    pub node_ref: NodeRef,
-    #[prop_or_default]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DroppableOptions {
+    /// Called when a dragged element enters the element.
     pub ondragenter: Callback<DragEvent>,
-    #[prop_or_default]
+    /// Called when a dragged element leaves the element.
     pub ondragleave: Callback<()>,
-    #[prop_or_default]
+    /// Called when a dragged element moves over the element.
     pub ondrag: Callback<DragEvent>,
-    #[prop_or_default]
+    /// Called when a dragged element is dropped on the element.
     pub ondrop: Callback<DragEvent>,
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DroppableState {
+    /// True while a dragged element is over the element.
+    pub is_over: bool,
+}
+
 #[inline]
 fn is_inside(node_ref: &NodeRef, x: i32, y: i32) -> bool {
-    let element = node_ref.cast::<HtmlElement>().unwrap();
+    let Some(element) = node_ref.cast::<HtmlElement>() else {
+        return false;
+    };
 
     let rect = element.get_bounding_client_rect();
 
@@ -364,52 +472,88 @@ fn is_inside(node_ref: &NodeRef, x: i32, y: i32) -> bool {
         && y <= rect.bottom() as i32
 }
 
-#[function_component]
-pub fn Droppable(props: &DroppableProps) -> Html {
-    let context = use_context::<DragContext>().expect("Droppable must be used inside DragArea");
-    let position = use_context::<DragPosition>().expect("Droppable must be used inside DragArea");
+/// Makes the element referenced by `node_ref` a drop target.
+///
+/// Must be used inside a [`DragArea`].
+#[hook]
+pub fn use_droppable(node_ref: NodeRef, options: DroppableOptions) -> DroppableState {
+    let context = use_context::<DragContext>().expect("use_droppable must be used inside DragArea");
+    let position =
+        use_context::<DragPosition>().expect("use_droppable must be used inside DragArea");
     let entered = use_state(|| false);
 
     use_effect_with_deps(
-        |(dragged, is_dragging, position, entered, props)| {
+        |(dragged, is_dragging, position, entered, node_ref, options)| {
             let event = DragEvent {
-                position: position.clone(),
+                position: *position,
                 node_ref: dragged.clone(),
             };
 
             if *is_dragging {
-                let inside = is_inside(&props.node_ref, position.x, position.y);
+                let inside = is_inside(node_ref, position.x, position.y);
 
                 if inside {
                     if !**entered {
-                        props.ondragenter.emit(event.clone());
+                        options.ondragenter.emit(event.clone());
                         entered.set(true);
                     }
 
-                    props.ondrag.emit(event);
-                } else {
-                    if **entered {
-                        props.ondragleave.emit(());
-                        entered.set(false);
-                    }
-                }
-            } else {
-                if **entered {
-                    props.ondrop.emit(event);
-                    props.ondragleave.emit(());
+                    options.ondrag.emit(event);
+                } else if **entered {
+                    options.ondragleave.emit(());
                     entered.set(false);
                 }
+            } else if **entered {
+                options.ondrop.emit(event);
+                options.ondragleave.emit(());
+                entered.set(false);
             }
         },
         (
             context.dragged.clone(),
-            context.is_dragging.clone(),
-            position.clone(),
+            context.is_dragging,
+            position,
             entered.clone(),
-            props.clone(),
+            node_ref,
+            options,
         ),
     );
 
+    DroppableState { is_over: *entered }
+}
+
+#[derive(Clone, Properties, PartialEq)]
+pub struct DroppableProps {
+    #[prop_or_default]
+    pub class: Classes,
+    #[prop_or_default]
+    pub style: String,
+    #[prop_or_default]
+    pub children: Children,
+    #[prop_or_default]
+    pub node_ref: NodeRef,
+    #[prop_or_default]
+    pub ondragenter: Callback<DragEvent>,
+    #[prop_or_default]
+    pub ondragleave: Callback<()>,
+    #[prop_or_default]
+    pub ondrag: Callback<DragEvent>,
+    #[prop_or_default]
+    pub ondrop: Callback<DragEvent>,
+}
+
+#[function_component]
+pub fn Droppable(props: &DroppableProps) -> Html {
+    use_droppable(
+        props.node_ref.clone(),
+        DroppableOptions {
+            ondragenter: props.ondragenter.clone(),
+            ondragleave: props.ondragleave.clone(),
+            ondrag: props.ondrag.clone(),
+            ondrop: props.ondrop.clone(),
+        },
+    );
+
     let mut style = Style::new()
         .with("width", "fit-content")
         .with("height", "fit-content");
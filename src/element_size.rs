@@ -0,0 +1,167 @@
+use std::{cell::RefCell, rc::Rc};
+
+use web_sys::{Element, Event};
+use yew::prelude::*;
+
+use crate::{EventListener, ResizeBox, ResizeObservation, Size, Timeout};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ElementSize {
+    pub width: f32,
+    pub height: f32,
+    /// The distance from the top of the viewport, when the element was last resized.
+    ///
+    /// It isn't updated when the element moves without being resized, like on scroll,
+    /// so it's only valid right after a resize.
+    pub top: f32,
+    /// The distance from the left of the viewport, when the element was last resized.
+    ///
+    /// Like `top`, it goes stale when the element moves without being resized.
+    pub left: f32,
+}
+
+impl ElementSize {
+    /// Measures the position of `element`, with an already observed `size`.
+    #[inline]
+    pub fn measure(element: &Element, size: Size) -> Self {
+        let rect = element.get_bounding_client_rect();
+
+        Self {
+            width: size.width,
+            height: size.height,
+            top: rect.top() as f32,
+            left: rect.left() as f32,
+        }
+    }
+
+    /// Measures the size of the viewport.
+    #[inline]
+    pub fn window() -> Self {
+        let window = web_sys::window().unwrap();
+
+        let width = window.inner_width().ok().and_then(|w| w.as_f64());
+        let height = window.inner_height().ok().and_then(|h| h.as_f64());
+
+        Self {
+            width: width.unwrap_or_default() as f32,
+            height: height.unwrap_or_default() as f32,
+            top: 0.0,
+            left: 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ElementSizeOptions {
+    /// The box of the element to observe.
+    pub resize_box: ResizeBox,
+    /// If set, the size is only updated once it hasn't changed for this many milliseconds.
+    pub debounce: Option<u32>,
+}
+
+/// Calls `f` after `debounce` milliseconds, or right away if not set.
+///
+/// A pending call is cancelled when `timeout` is replaced or dropped.
+fn debounced(
+    timeout: &RefCell<Option<Timeout>>,
+    debounce: Option<u32>,
+    f: impl FnOnce() + 'static,
+) {
+    match debounce {
+        Some(ms) => {
+            timeout.borrow_mut().replace(Timeout::new(ms, f));
+        }
+        None => f(),
+    }
+}
+
+/// Gets the size of the element referenced by `node_ref`, updated when it's resized.
+///
+/// Its `top` and `left` are only measured on a resize too, measure the element again when
+/// its current position is needed.
+///
+/// Built on the same shared observer as [`DetectResize`](crate::DetectResize).
+#[hook]
+pub fn use_element_size(node_ref: NodeRef, options: ElementSizeOptions) -> ElementSize {
+    let size = use_state_eq(ElementSize::default);
+
+    {
+        let size = size.clone();
+
+        use_effect_with_deps(
+            move |(node_ref, options)| {
+                let timeout = Rc::new(RefCell::new(None));
+
+                let observation = node_ref.cast::<Element>().map(|element| {
+                    let timeout = timeout.clone();
+                    let debounce = options.debounce;
+                    let measured = element.clone();
+
+                    let callback = Callback::from(move |new_size: Size| {
+                        let size = size.clone();
+                        let element = measured.clone();
+
+                        debounced(&timeout, debounce, move || {
+                            size.set(ElementSize::measure(&element, new_size));
+                        });
+                    });
+
+                    ResizeObservation::new(&element, options.resize_box, callback)
+                });
+
+                move || {
+                    drop(observation);
+                    timeout.borrow_mut().take();
+                }
+            },
+            (node_ref, options),
+        );
+    }
+
+    *size
+}
+
+/// Gets the size of the viewport, updated when the window is resized.
+///
+/// The size is zero until mounted, which keeps rendering on the server the same as the
+/// first render in the browser.
+///
+/// If `debounce` is set, the size is only updated once it hasn't changed for that many milliseconds.
+#[hook]
+pub fn use_window_size(debounce: Option<u32>) -> ElementSize {
+    let size = use_state_eq(ElementSize::default);
+
+    {
+        let size = size.clone();
+
+        use_effect_with_deps(
+            move |debounce| {
+                let debounce = *debounce;
+                let timeout = Rc::new(RefCell::new(None));
+                let window = web_sys::window().unwrap();
+
+                size.set(ElementSize::window());
+
+                let listener = {
+                    let timeout = timeout.clone();
+
+                    EventListener::new(&window, "resize", move |_: Event| {
+                        let size = size.clone();
+
+                        debounced(&timeout, debounce, move || {
+                            size.set(ElementSize::window());
+                        });
+                    })
+                };
+
+                move || {
+                    drop(listener);
+                    timeout.borrow_mut().take();
+                }
+            },
+            debounce,
+        );
+    }
+
+    *size
+}
@@ -0,0 +1,42 @@
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{Event, EventTarget};
+
+/// An event listener attached to an [`EventTarget`].
+///
+/// The listener is removed when dropped.
+pub struct EventListener {
+    target: EventTarget,
+    event_type: &'static str,
+    closure: Closure<dyn FnMut(Event)>,
+}
+
+impl EventListener {
+    pub fn new<E, F>(target: &EventTarget, event_type: &'static str, mut f: F) -> Self
+    where
+        E: JsCast,
+        F: FnMut(E) + 'static,
+    {
+        let closure = Closure::<dyn FnMut(Event)>::new(move |event: Event| {
+            f(event.unchecked_into());
+        });
+
+        target
+            .add_event_listener_with_callback(event_type, closure.as_ref().unchecked_ref())
+            .expect("garlic failed to add event listener");
+
+        Self {
+            target: target.clone(),
+            event_type,
+            closure,
+        }
+    }
+}
+
+impl Drop for EventListener {
+    fn drop(&mut self) {
+        let _ = self.target.remove_event_listener_with_callback(
+            self.event_type,
+            self.closure.as_ref().unchecked_ref(),
+        );
+    }
+}
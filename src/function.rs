@@ -1,6 +1,14 @@
+use std::{
+    cell::{Cell, RefCell},
+    mem,
+    rc::Rc,
+};
+
 use wasm_bindgen::{closure::Closure, JsCast};
 use yew::prelude::*;
 
+use crate::clock;
+
 pub struct Function {
     pub closure: Closure<dyn FnMut()>,
 }
@@ -36,7 +44,7 @@ impl AsRef<js_sys::Function> for Function {
 }
 
 pub struct AnimationFrame {
-    pub function: Function,
+    function: Box<dyn FnOnce(f64)>,
 }
 
 impl AnimationFrame {
@@ -44,18 +52,45 @@ impl AnimationFrame {
     pub fn new<F>(f: F) -> Self
     where
         F: FnOnce() + 'static,
+    {
+        Self::with_timestamp(move |_| f())
+    }
+
+    /// Creates an animation frame that is called with the time the frame started.
+    #[inline]
+    pub fn with_timestamp<F>(f: F) -> Self
+    where
+        F: FnOnce(f64) + 'static,
     {
         Self {
-            function: Function::once(f),
+            function: Box::new(f),
         }
     }
 
+    /// Requests the frame, it's cancelled if the returned handle is dropped before then.
     #[inline]
-    pub fn request(self) {
-        web_sys::window()
-            .unwrap()
-            .request_animation_frame(&self.function.into_js())
-            .expect("garlic failed to request animation frame");
+    pub fn request(self) -> AnimationFrameHandle {
+        let id = clock().request_animation_frame(self.function);
+        AnimationFrameHandle { id }
+    }
+}
+
+/// A requested [`AnimationFrame`], cancelled when dropped.
+#[must_use = "the animation frame is cancelled when the handle is dropped"]
+#[derive(Debug, PartialEq)]
+pub struct AnimationFrameHandle {
+    id: i32,
+}
+
+impl AnimationFrameHandle {
+    pub fn forget(self) {
+        mem::forget(self);
+    }
+}
+
+impl Drop for AnimationFrameHandle {
+    fn drop(&mut self) {
+        clock().cancel_animation_frame(self.id);
     }
 }
 
@@ -64,3 +99,86 @@ pub fn use_animation_frame() -> AnimationFrame {
     let update = use_force_update();
     AnimationFrame::new(move || update.force_update())
 }
+
+type RafFunction = Box<dyn FnMut(f64, f64)>;
+
+struct RafLoop {
+    running: Cell<bool>,
+    last: Cell<Option<f64>>,
+    handle: RefCell<Option<AnimationFrameHandle>>,
+    /// Taken while running, so it can be replaced by a render caused by the function.
+    function: Rc<RefCell<Option<RafFunction>>>,
+}
+
+impl RafLoop {
+    fn request(self: &Rc<Self>) {
+        let this = self.clone();
+        let frame = AnimationFrame::with_timestamp(move |timestamp| this.frame(timestamp));
+
+        self.handle.borrow_mut().replace(frame.request());
+    }
+
+    fn frame(self: &Rc<Self>, timestamp: f64) {
+        if !self.running.get() {
+            return;
+        }
+
+        let delta = timestamp - self.last.get().unwrap_or(timestamp);
+        self.last.set(Some(timestamp));
+
+        let function = self.function.borrow_mut().take();
+
+        if let Some(mut function) = function {
+            function(timestamp, delta);
+
+            self.function.borrow_mut().get_or_insert(function);
+        }
+
+        // the loop may have been stopped by the function
+        if self.running.get() {
+            self.request();
+        }
+    }
+
+    fn stop(&self) {
+        self.running.set(false);
+        self.handle.borrow_mut().take();
+    }
+}
+
+/// Calls `f` every frame while `active`, with the timestamp of the frame
+/// and the milliseconds since the previous one.
+///
+/// The delta of the first frame is zero. The loop stops on unmount.
+#[hook]
+pub fn use_raf_loop<F>(f: F, active: bool)
+where
+    F: FnMut(f64, f64) + 'static,
+{
+    // the latest `f` is always called, without restarting the loop
+    let function = use_mut_ref(|| None::<RafFunction>);
+    *function.borrow_mut() = Some(Box::new(f));
+
+    use_effect_with_deps(
+        move |active| {
+            let raf_loop = active.then(|| {
+                let raf_loop = Rc::new(RafLoop {
+                    running: Cell::new(true),
+                    last: Cell::new(None),
+                    handle: RefCell::new(None),
+                    function,
+                });
+
+                raf_loop.request();
+                raf_loop
+            });
+
+            move || {
+                if let Some(raf_loop) = raf_loop {
+                    raf_loop.stop();
+                }
+            }
+        },
+        active,
+    );
+}
@@ -1,22 +1,21 @@
-use std::mem;
+use std::{mem, rc::Rc};
 
-use crate::function::Function;
+use yew::prelude::*;
 
+use crate::clock;
+
+/// Calls a function every `ms` milliseconds, until dropped.
 #[derive(Debug, PartialEq)]
 pub struct Interval {
     id: i32,
 }
 
 impl Interval {
-    pub fn new<F>(f: F) -> Self
+    pub fn new<F>(ms: u32, f: F) -> Self
     where
         F: FnMut() + 'static,
     {
-        let function = Function::new(f);
-        let id = web_sys::window()
-            .unwrap()
-            .set_interval_with_callback(&function.into_js())
-            .expect("garlic failed to set interval");
+        let id = clock().set_interval(ms, Box::new(f));
 
         Self { id }
     }
@@ -28,8 +27,34 @@ impl Interval {
 
 impl Drop for Interval {
     fn drop(&mut self) {
-        web_sys::window()
-            .unwrap()
-            .clear_interval_with_handle(self.id);
+        clock().clear_interval(self.id);
     }
 }
+
+/// Calls `f` every `ms` milliseconds while the component is mounted.
+///
+/// If `ms` is `None` the interval is paused.
+#[hook]
+pub fn use_interval<F>(f: F, ms: Option<u32>)
+where
+    F: Fn() + 'static,
+{
+    // the latest `f` is always called, without restarting the interval
+    let f = Rc::new(f) as Rc<dyn Fn()>;
+    let latest = use_mut_ref(|| f.clone());
+    *latest.borrow_mut() = f;
+
+    use_effect_with_deps(
+        move |ms| {
+            let interval = ms.map(|ms| {
+                Interval::new(ms, move || {
+                    let f = latest.borrow().clone();
+                    f();
+                })
+            });
+
+            move || drop(interval)
+        },
+        ms,
+    );
+}
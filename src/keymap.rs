@@ -0,0 +1,291 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+#[cfg(feature = "input")]
+use web_sys::KeyboardEvent;
+
+/// The modifier keys held with a key.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+    pub meta: bool,
+}
+
+impl Modifiers {
+    #[cfg(feature = "input")]
+    #[inline]
+    pub fn from_event(event: &KeyboardEvent) -> Self {
+        Self {
+            ctrl: event.ctrl_key(),
+            alt: event.alt_key(),
+            shift: event.shift_key(),
+            meta: event.meta_key(),
+        }
+    }
+}
+
+/// A key with modifiers, like `Ctrl+Backspace`.
+///
+/// Keys are [`KeyboardEvent.key`](https://developer.mozilla.org/docs/Web/API/KeyboardEvent/key)
+/// values, compared ignoring ascii case since Shift changes the case of letters.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub key: String,
+    pub modifiers: Modifiers,
+}
+
+impl KeyBinding {
+    #[inline]
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            modifiers: Modifiers::default(),
+        }
+    }
+
+    #[inline]
+    pub fn ctrl(mut self) -> Self {
+        self.modifiers.ctrl = true;
+        self
+    }
+
+    #[inline]
+    pub fn alt(mut self) -> Self {
+        self.modifiers.alt = true;
+        self
+    }
+
+    #[inline]
+    pub fn shift(mut self) -> Self {
+        self.modifiers.shift = true;
+        self
+    }
+
+    #[inline]
+    pub fn meta(mut self) -> Self {
+        self.modifiers.meta = true;
+        self
+    }
+
+    /// Returns true if `key` pressed with exactly `modifiers` triggers the binding.
+    #[inline]
+    pub fn matches(&self, key: &str, modifiers: Modifiers) -> bool {
+        self.modifiers == modifiers && self.key.eq_ignore_ascii_case(key)
+    }
+
+    #[cfg(feature = "input")]
+    #[inline]
+    pub fn matches_event(&self, event: &KeyboardEvent) -> bool {
+        self.matches(&event.key(), Modifiers::from_event(event))
+    }
+}
+
+impl Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Modifiers {
+            ctrl,
+            alt,
+            shift,
+            meta,
+        } = self.modifiers;
+
+        for (held, name) in [
+            (ctrl, "Ctrl"),
+            (alt, "Alt"),
+            (shift, "Shift"),
+            (meta, "Meta"),
+        ] {
+            if held {
+                write!(f, "{}+", name)?;
+            }
+        }
+
+        write!(f, "{}", self.key)
+    }
+}
+
+/// An error produced when parsing a malformed [`KeyBinding`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseKeyBindingError {
+    EmptyKey,
+    UnknownModifier(String),
+}
+
+impl Display for ParseKeyBindingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseKeyBindingError::EmptyKey => write!(f, "key binding has no key"),
+            ParseKeyBindingError::UnknownModifier(modifier) => {
+                write!(f, "unknown modifier {:?}", modifier)
+            }
+        }
+    }
+}
+
+impl Error for ParseKeyBindingError {}
+
+impl FromStr for KeyBinding {
+    type Err = ParseKeyBindingError;
+
+    /// Parses bindings like `Escape`, `Ctrl+Backspace` or `Ctrl++`.
+    ///
+    /// Modifiers are `Ctrl` or `Control`, `Alt` or `Option`, `Shift`, and `Meta` or `Cmd`,
+    /// in any case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // `+` is both the separator and a key
+        let (modifiers, key) = match (s, s.strip_suffix("++")) {
+            ("+", _) => (None, s),
+            (_, Some(modifiers)) => (Some(modifiers), "+"),
+            _ => match s.rsplit_once('+') {
+                Some((modifiers, key)) => (Some(modifiers), key),
+                None => (None, s),
+            },
+        };
+
+        if key.is_empty() {
+            return Err(ParseKeyBindingError::EmptyKey);
+        }
+
+        let mut binding = KeyBinding::new(key);
+
+        for modifier in modifiers.into_iter().flat_map(|m| m.split('+')) {
+            binding = match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => binding.ctrl(),
+                "alt" | "option" => binding.alt(),
+                "shift" => binding.shift(),
+                "meta" | "cmd" => binding.meta(),
+                _ => return Err(ParseKeyBindingError::UnknownModifier(modifier.to_owned())),
+            };
+        }
+
+        Ok(binding)
+    }
+}
+
+/// What an input does on a shortcut, see [`Keymap`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputAction {
+    /// Empties the input.
+    Clear,
+    /// Removes the focus from the input.
+    Blur,
+}
+
+/// The keyboard shortcuts of garlic inputs, handled on `keydown`.
+///
+/// The default keymap is empty, so the browser's own shortcuts are kept.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Keymap {
+    bindings: Vec<(KeyBinding, InputAction)>,
+}
+
+impl Keymap {
+    /// Creates a keymap without shortcuts.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+        }
+    }
+
+    /// The default keymap of a [`SearchQuery`](crate::SearchQuery), clearing the query with
+    /// Escape or Ctrl+Backspace.
+    pub fn search() -> Self {
+        Self::new()
+            .bind(KeyBinding::new("Backspace").ctrl(), InputAction::Clear)
+            .bind(KeyBinding::new("Escape"), InputAction::Clear)
+    }
+
+    /// Adds a shortcut, taking precedence over the existing ones for the same key.
+    #[inline]
+    pub fn bind(mut self, binding: KeyBinding, action: InputAction) -> Self {
+        self.bindings.insert(0, (binding, action));
+        self
+    }
+
+    /// Removes every shortcut of `binding`.
+    #[inline]
+    pub fn unbind(mut self, binding: &KeyBinding) -> Self {
+        self.bindings.retain(|(b, _)| b != binding);
+        self
+    }
+
+    #[inline]
+    pub fn bindings(&self) -> &[(KeyBinding, InputAction)] {
+        &self.bindings
+    }
+
+    /// Gets the action of `key` pressed with `modifiers`, if any.
+    pub fn action(&self, key: &str, modifiers: Modifiers) -> Option<InputAction> {
+        self.bindings
+            .iter()
+            .find(|(binding, _)| binding.matches(key, modifiers))
+            .map(|&(_, action)| action)
+    }
+
+    #[cfg(feature = "input")]
+    #[inline]
+    pub fn event_action(&self, event: &KeyboardEvent) -> Option<InputAction> {
+        self.action(&event.key(), Modifiers::from_event(event))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_key_bindings() {
+        assert_eq!("Escape".parse(), Ok(KeyBinding::new("Escape")));
+        assert_eq!(
+            "ctrl+Shift+z".parse(),
+            Ok(KeyBinding::new("z").ctrl().shift())
+        );
+        assert_eq!("Ctrl++".parse(), Ok(KeyBinding::new("+").ctrl()));
+        assert_eq!("+".parse(), Ok(KeyBinding::new("+")));
+
+        assert_eq!(
+            "Ctrl+".parse::<KeyBinding>(),
+            Err(ParseKeyBindingError::EmptyKey)
+        );
+        assert_eq!(
+            "Hyper+a".parse::<KeyBinding>(),
+            Err(ParseKeyBindingError::UnknownModifier("Hyper".to_owned()))
+        );
+
+        let binding = KeyBinding::new("Backspace").ctrl().alt();
+        assert_eq!(binding.to_string().parse(), Ok(binding));
+    }
+
+    #[test]
+    fn keymap_matches_exact_modifiers() {
+        assert!(Keymap::default().bindings().is_empty());
+
+        let keymap = Keymap::search();
+        let ctrl = Modifiers {
+            ctrl: true,
+            ..Modifiers::default()
+        };
+
+        assert_eq!(
+            keymap.action("Escape", Modifiers::default()),
+            Some(InputAction::Clear)
+        );
+        assert_eq!(keymap.action("Backspace", ctrl), Some(InputAction::Clear));
+        assert_eq!(keymap.action("Backspace", Modifiers::default()), None);
+
+        let keymap = keymap.bind(KeyBinding::new("Escape"), InputAction::Blur);
+        assert_eq!(
+            keymap.action("escape", Modifiers::default()),
+            Some(InputAction::Blur)
+        );
+
+        let keymap = keymap.unbind(&KeyBinding::new("Escape"));
+        assert_eq!(keymap.action("Escape", Modifiers::default()), None);
+    }
+}
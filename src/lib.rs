@@ -1,23 +1,114 @@
+//! Components and utilities for Yew.
+//!
+//! Everything is enabled by default. The pure Rust parts, like [`Order`], [`Style`] and
+//! [`Direction`], are always available and don't depend on wasm, the rest is split into
+//! cargo features:
+//!
+//! - `timers`, timers, animation frames and the hooks built on them.
+//! - `resize`, observing the size of elements.
+//! - `style`, the garlic stylesheet and applying styles to the DOM.
+//! - `drag`, draggable and droppable elements.
+//! - `sortable`, sortable lists and tables.
+//! - `input`, text inputs, search and forms.
+
+mod animation;
+#[cfg(feature = "timers")]
+mod clock;
+mod color;
+mod css;
+#[cfg(feature = "timers")]
+mod debounce;
+#[cfg(feature = "resize")]
 mod detect_resize;
 mod direction;
+#[cfg(feature = "drag")]
 mod drag;
+#[cfg(feature = "resize")]
+mod element_size;
+#[cfg(feature = "timers")]
+mod event_listener;
+#[cfg(feature = "timers")]
 mod function;
+#[cfg(feature = "timers")]
 mod interval;
+mod keymap;
 mod order;
+mod order_history;
+#[cfg(feature = "input")]
+mod parsed_input;
+mod rank;
+#[cfg(feature = "resize")]
+mod resize_observer;
+mod search;
+mod search_history;
 mod search_query;
+#[cfg(feature = "sortable")]
 mod sortable_list;
+#[cfg(feature = "sortable")]
+mod sortable_table;
+#[cfg(feature = "sortable")]
 mod spacer;
 mod style;
+#[cfg(feature = "style")]
+mod style_dom;
+mod style_parser;
+#[cfg(feature = "style")]
+mod stylesheet;
+#[cfg(feature = "input")]
 mod text_input;
+#[cfg(feature = "timers")]
+mod throttle;
+#[cfg(feature = "timers")]
+mod timeout;
+mod validation;
 
+pub use animation::*;
+#[cfg(feature = "timers")]
+pub use clock::*;
+pub use color::*;
+pub use css::*;
+#[cfg(feature = "timers")]
+pub use debounce::*;
+#[cfg(feature = "resize")]
 pub use detect_resize::*;
 pub use direction::*;
+#[cfg(feature = "drag")]
 pub use drag::*;
+#[cfg(feature = "resize")]
+pub use element_size::*;
+#[cfg(feature = "timers")]
+pub use event_listener::*;
+#[cfg(feature = "timers")]
 pub use function::*;
+#[cfg(feature = "timers")]
 pub use interval::*;
+pub use keymap::*;
 pub use order::*;
+pub use order_history::*;
+#[cfg(feature = "input")]
+pub use parsed_input::*;
+pub use rank::*;
+#[cfg(feature = "resize")]
+pub use resize_observer::*;
+pub use search::*;
+pub use search_history::*;
 pub use search_query::*;
+#[cfg(feature = "sortable")]
 pub use sortable_list::*;
+#[cfg(feature = "sortable")]
+pub use sortable_table::*;
+#[cfg(feature = "sortable")]
 pub use spacer::*;
 pub use style::*;
+#[cfg(feature = "style")]
+pub use style_dom::*;
+pub use style_parser::*;
+#[cfg(feature = "style")]
+pub use stylesheet::*;
+#[cfg(feature = "input")]
 pub use text_input::*;
+#[cfg(feature = "timers")]
+pub use throttle::*;
+#[cfg(feature = "timers")]
+pub use timeout::*;
+pub use validation::*;
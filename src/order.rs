@@ -1,8 +1,69 @@
-use std::ops::Deref;
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    ops::Deref,
+};
 
+/// The order of a list of items, where `order[index]` is the item shown at `index`.
+///
+/// An order is always a permutation of `0..len`.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Order {
     indices: Vec<usize>,
+    /// The inverse of `indices`, where `positions[item]` is the index `item` is shown at.
+    positions: Vec<usize>,
+}
+
+/// An error produced when creating an [`Order`] from indices that aren't a permutation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OrderError {
+    OutOfBounds { item: usize, len: usize },
+    Duplicate { item: usize },
+}
+
+impl Display for OrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderError::OutOfBounds { item, len } => {
+                write!(
+                    f,
+                    "item {} is out of bounds for an order of {} items",
+                    item, len
+                )
+            }
+            OrderError::Duplicate { item } => write!(f, "item {} appears more than once", item),
+        }
+    }
+}
+
+impl Error for OrderError {}
+
+/// A move of a single item, as done by [`Order::swap_move`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OrderMove {
+    pub from: usize,
+    pub to: usize,
+}
+
+impl OrderMove {
+    /// Gets the move that undoes this one.
+    #[inline]
+    pub fn inverse(self) -> Self {
+        // where the item ended up
+        let at = if self.from < self.to {
+            self.to - 1
+        } else {
+            self.to
+        };
+
+        let to = if at < self.from {
+            self.from + 1
+        } else {
+            self.from
+        };
+
+        Self { from: at, to }
+    }
 }
 
 impl Order {
@@ -10,25 +71,97 @@ impl Order {
     pub const fn new() -> Self {
         Self {
             indices: Vec::new(),
+            positions: Vec::new(),
+        }
+    }
+
+    /// Creates an order from `indices`, which must be a permutation of `0..indices.len()`.
+    pub fn from_vec(indices: Vec<usize>) -> Result<Self, OrderError> {
+        let len = indices.len();
+        let mut positions = vec![usize::MAX; len];
+
+        for (index, &item) in indices.iter().enumerate() {
+            if item >= len {
+                return Err(OrderError::OutOfBounds { item, len });
+            }
+
+            if positions[item] != usize::MAX {
+                return Err(OrderError::Duplicate { item });
+            }
+
+            positions[item] = index;
+        }
+
+        Ok(Self { indices, positions })
+    }
+
+    #[inline]
+    pub fn into_vec(self) -> Vec<usize> {
+        self.indices
+    }
+
+    /// Gets the index `item` is shown at.
+    #[inline]
+    pub fn index_of(&self, item: usize) -> Option<usize> {
+        self.positions.get(item).copied()
+    }
+
+    fn update_positions(&mut self, range: impl IntoIterator<Item = usize>) {
+        for index in range {
+            self.positions[self.indices[index]] = index;
         }
     }
 
+    /// Resizes the order to `size` items.
+    ///
+    /// New items are added at the end. When shrinking, only the first `size` shown items are
+    /// kept, renumbered so the order stays a permutation.
     #[inline]
     pub fn resize(&mut self, size: usize) {
-        if self.indices.len() == size {
+        if size < self.indices.len() {
+            self.indices.truncate(size);
+
+            // renumber the items by rank, keeping their order
+            let mut items = self.indices.clone();
+            items.sort_unstable();
+
+            for item in &mut self.indices {
+                *item = items.binary_search(item).unwrap();
+            }
+
+            self.positions.truncate(size);
+            self.update_positions(0..size);
             return;
         }
 
+        self.grow(size);
+    }
+
+    /// Resizes the order to the items `0..size`.
+    ///
+    /// New items are added at the end, removed items keep the order of the rest.
+    #[inline]
+    pub fn resize_items(&mut self, size: usize) {
         if size < self.indices.len() {
-            self.indices.truncate(size);
+            self.indices.retain(|&item| item < size);
+            self.positions.truncate(size);
+            self.update_positions(0..size);
             return;
         }
 
+        self.grow(size);
+    }
+
+    fn grow(&mut self, size: usize) {
         for i in self.indices.len()..size {
             self.indices.push(i);
+            self.positions.push(i);
         }
     }
 
+    /// Moves the item at `from` to be in front of the item at `to`.
+    ///
+    /// `to` may be `len`, to move the item to the end.
     #[inline]
     pub fn swap_move(&mut self, from: usize, mut to: usize) {
         if from == to {
@@ -41,8 +174,115 @@ impl Order {
 
         let index = self.indices.remove(from);
         self.indices.insert(to, index);
+
+        self.update_positions(from.min(to)..=from.max(to));
+    }
+
+    /// Inserts a new item at `index`, returning it.
+    ///
+    /// The new item is `len`, like the item added by [`Order::resize`].
+    #[inline]
+    pub fn insert_at(&mut self, index: usize) -> usize {
+        let item = self.indices.len();
+
+        self.indices.insert(index, item);
+        self.positions.push(index);
+        self.update_positions(index..self.indices.len());
+
+        item
+    }
+
+    /// Removes the item at `index`, returning it.
+    ///
+    /// Items after the removed item are renumbered, so the order stays a permutation.
+    #[inline]
+    pub fn remove_at(&mut self, index: usize) -> usize {
+        let removed = self.indices.remove(index);
+
+        for item in &mut self.indices {
+            if *item > removed {
+                *item -= 1;
+            }
+        }
+
+        self.positions.remove(removed);
+        self.update_positions(index..self.indices.len());
+
+        removed
+    }
+
+    /// Gets the moves that turn this order into `other`.
+    ///
+    /// As few items as possible are moved, applying the moves in order with
+    /// [`Order::swap_move`] gives `other`.
+    ///
+    /// # Panics
+    /// Panics if the orders aren't the same length.
+    #[track_caller]
+    pub fn diff(&self, other: &Order) -> Vec<OrderMove> {
+        assert_eq!(self.len(), other.len(), "orders must be the same length");
+
+        let stable = longest_increasing(
+            &self
+                .indices
+                .iter()
+                .map(|&item| other.positions[item])
+                .collect::<Vec<_>>(),
+        );
+
+        let mut is_stable = vec![false; self.len()];
+        for index in stable {
+            is_stable[self.indices[index]] = true;
+        }
+
+        let mut current = self.clone();
+        let mut moves = Vec::new();
+
+        // every moved item is placed right after the item before it in `other`,
+        // which is already in place
+        for (target, &item) in other.indices.iter().enumerate() {
+            if is_stable[item] {
+                continue;
+            }
+
+            let from = current.positions[item];
+            let to = match target {
+                0 => 0,
+                _ => current.positions[other.indices[target - 1]] + 1,
+            };
+
+            if from != to {
+                current.swap_move(from, to);
+                moves.push(OrderMove { from, to });
+            }
+        }
+
+        moves
     }
 
+    /// Gets `items` in this order, where `result[index] = items[order[index]]`.
+    #[inline]
+    #[track_caller]
+    pub fn apply_to_vec<T: Clone>(&self, items: &[T]) -> Vec<T> {
+        assert_eq!(
+            items.len(),
+            self.indices.len(),
+            "items and indices must be the same length"
+        );
+
+        self.indices
+            .iter()
+            .map(|&item| items[item].clone())
+            .collect()
+    }
+
+    /// Moves every item in place to where the order shows it, `items[index]` goes to
+    /// `order[index]`.
+    ///
+    /// This is the inverse of [`Order::gather`].
+    ///
+    /// # Panics
+    /// Panics if `items` isn't the same length as the order.
     #[inline]
     #[track_caller]
     pub fn apply<T>(mut self, items: &mut [T]) {
@@ -61,6 +301,69 @@ impl Order {
             }
         }
     }
+
+    /// Reorders `items` in place, like [`Order::apply_to_vec`].
+    ///
+    /// # Panics
+    /// Panics if `items` isn't the same length as the order.
+    #[inline]
+    #[track_caller]
+    pub fn gather<T>(mut self, items: &mut [T]) {
+        assert_eq!(
+            items.len(),
+            self.indices.len(),
+            "items and indices must be the same length"
+        );
+
+        // follow every cycle of the permutation, marking visited indices as in place
+        for start in 0..items.len() {
+            let mut index = start;
+
+            loop {
+                let next = self.indices[index];
+                self.indices[index] = index;
+
+                if next == start {
+                    break;
+                }
+
+                items.swap(index, next);
+                index = next;
+            }
+        }
+    }
+}
+
+/// Gets the indices of a longest strictly increasing subsequence of `values`.
+fn longest_increasing(values: &[usize]) -> Vec<usize> {
+    // `tails[len]` is the index of the smallest value ending an increasing run of `len + 1`
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous = vec![None; values.len()];
+
+    for (index, &value) in values.iter().enumerate() {
+        let len = tails.partition_point(|&tail| values[tail] < value);
+
+        if len > 0 {
+            previous[index] = Some(tails[len - 1]);
+        }
+
+        if len == tails.len() {
+            tails.push(index);
+        } else {
+            tails[len] = index;
+        }
+    }
+
+    let mut result = Vec::with_capacity(tails.len());
+    let mut index = tails.last().copied();
+
+    while let Some(i) = index {
+        result.push(i);
+        index = previous[i];
+    }
+
+    result.reverse();
+    result
 }
 
 impl Deref for Order {
@@ -71,3 +374,193 @@ impl Deref for Order {
         &self.indices
     }
 }
+
+impl TryFrom<Vec<usize>> for Order {
+    type Error = OrderError;
+
+    #[inline]
+    fn try_from(indices: Vec<usize>) -> Result<Self, Self::Error> {
+        Self::from_vec(indices)
+    }
+}
+
+impl From<Order> for Vec<usize> {
+    #[inline]
+    fn from(order: Order) -> Self {
+        order.into_vec()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Order {
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.indices.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Order {
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let indices = Vec::<usize>::deserialize(deserializer)?;
+        Self::from_vec(indices).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(indices: &[usize]) -> Order {
+        Order::from_vec(indices.to_vec()).unwrap()
+    }
+
+    fn assert_consistent(order: &Order) {
+        for (index, &item) in order.iter().enumerate() {
+            assert_eq!(order.index_of(item), Some(index), "{:?}", order);
+        }
+    }
+
+    /// Shuffles `0..len` with a small lcg.
+    fn shuffled(len: usize, seed: &mut u64) -> Order {
+        let mut indices = (0..len).collect::<Vec<_>>();
+
+        for i in (1..len).rev() {
+            *seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            indices.swap(i, (*seed >> 33) as usize % (i + 1));
+        }
+
+        order(&indices)
+    }
+
+    #[test]
+    fn from_vec_validates_permutation() {
+        assert!(Order::from_vec(vec![2, 0, 1]).is_ok());
+        assert_eq!(
+            Order::from_vec(vec![0, 3, 1]),
+            Err(OrderError::OutOfBounds { item: 3, len: 3 })
+        );
+        assert_eq!(
+            Order::from_vec(vec![0, 1, 1]),
+            Err(OrderError::Duplicate { item: 1 })
+        );
+    }
+
+    #[test]
+    fn mutations_keep_positions() {
+        let mut order = Order::new();
+        order.resize(5);
+        assert_consistent(&order);
+
+        order.swap_move(0, 3);
+        assert_eq!(*order, [1, 2, 0, 3, 4]);
+        assert_consistent(&order);
+
+        order.swap_move(4, 1);
+        assert_eq!(*order, [1, 4, 2, 0, 3]);
+        assert_consistent(&order);
+
+        assert_eq!(order.insert_at(2), 5);
+        assert_eq!(*order, [1, 4, 5, 2, 0, 3]);
+        assert_consistent(&order);
+
+        assert_eq!(order.remove_at(3), 2);
+        assert_eq!(*order, [1, 3, 4, 0, 2]);
+        assert_consistent(&order);
+
+        let mut truncated = order.clone();
+        truncated.resize(3);
+        assert_eq!(*truncated, [0, 1, 2]);
+        assert_consistent(&truncated);
+
+        order.resize_items(3);
+        assert_eq!(*order, [1, 0, 2]);
+        assert_consistent(&order);
+    }
+
+    #[test]
+    fn apply_scatters_items() {
+        let mut items = ['a', 'b', 'c'];
+        order(&[1, 2, 0]).apply(&mut items);
+        assert_eq!(items, ['c', 'a', 'b']);
+
+        let mut items = ['a', 'b', 'c'];
+        order(&[1, 2, 0]).gather(&mut items);
+        assert_eq!(items, ['b', 'c', 'a']);
+    }
+
+    #[test]
+    fn gather_matches_apply_to_vec() {
+        let mut seed = 1;
+
+        for len in 0..8 {
+            for _ in 0..10 {
+                let order = shuffled(len, &mut seed);
+                let items = (0..len).map(|i| i * 10).collect::<Vec<_>>();
+
+                let expected = order.apply_to_vec(&items);
+                assert!(expected
+                    .iter()
+                    .zip(order.iter())
+                    .all(|(&a, &b)| a == b * 10));
+
+                let mut gathered = items.clone();
+                order.clone().gather(&mut gathered);
+                assert_eq!(gathered, expected);
+
+                let mut applied = expected;
+                order.apply(&mut applied);
+                assert_eq!(applied, items);
+            }
+        }
+    }
+
+    #[test]
+    fn diff_moves_are_minimal() {
+        assert!(order(&[0, 1, 2]).diff(&order(&[0, 1, 2])).is_empty());
+        assert_eq!(
+            order(&[0, 1, 2, 3]).diff(&order(&[1, 2, 3, 0])),
+            [OrderMove { from: 0, to: 4 }]
+        );
+
+        let mut seed = 7;
+
+        for len in 0..10 {
+            for _ in 0..20 {
+                let from = shuffled(len, &mut seed);
+                let to = shuffled(len, &mut seed);
+
+                let moves = from.diff(&to);
+
+                let values = from
+                    .iter()
+                    .map(|&item| to.positions[item])
+                    .collect::<Vec<_>>();
+                assert_eq!(moves.len(), len - longest_increasing(&values).len());
+
+                let mut current = from.clone();
+                for OrderMove { from, to } in moves {
+                    current.swap_move(from, to);
+                }
+
+                assert_eq!(current, to);
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_validates_permutation() {
+        use serde::{de::value::SeqDeserializer, Deserialize};
+
+        fn deserialize(indices: Vec<usize>) -> Result<Order, serde::de::value::Error> {
+            Order::deserialize(SeqDeserializer::new(indices.into_iter()))
+        }
+
+        assert_eq!(deserialize(vec![1, 0]).unwrap(), order(&[1, 0]));
+        assert!(deserialize(vec![1, 1]).is_err());
+    }
+}
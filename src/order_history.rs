@@ -0,0 +1,411 @@
+use std::collections::VecDeque;
+
+#[cfg(feature = "sortable")]
+use std::{cell::RefCell, rc::Rc};
+
+#[cfg(feature = "sortable")]
+use wasm_bindgen::JsCast;
+#[cfg(feature = "sortable")]
+use web_sys::HtmlElement;
+#[cfg(feature = "sortable")]
+use yew::prelude::*;
+
+#[cfg(feature = "sortable")]
+use crate::EventListener;
+use crate::{Order, OrderMove};
+
+/// An [`Order`] that records its moves, so they can be undone and redone.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OrderHistory {
+    order: Order,
+    undo: VecDeque<OrderMove>,
+    redo: Vec<OrderMove>,
+    capacity: usize,
+    /// Bumped on every change.
+    version: u64,
+}
+
+impl OrderHistory {
+    /// The number of moves kept by default.
+    pub const DEFAULT_CAPACITY: usize = 100;
+
+    #[inline]
+    pub fn new(order: Order) -> Self {
+        Self::with_capacity(order, Self::DEFAULT_CAPACITY)
+    }
+
+    /// Creates a history that keeps at most `capacity` moves, dropping the oldest first.
+    #[inline]
+    pub fn with_capacity(order: Order, capacity: usize) -> Self {
+        Self {
+            order,
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            capacity,
+            version: 0,
+        }
+    }
+
+    #[inline]
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        while self.undo.len() > self.capacity {
+            self.undo.pop_front();
+        }
+    }
+
+    #[inline]
+    pub fn order(&self) -> &Order {
+        &self.order
+    }
+
+    #[inline]
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    #[inline]
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    #[inline]
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Moves an item like [`Order::swap_move`], recording the move and clearing the redo stack.
+    pub fn swap_move(&mut self, from: usize, to: usize) {
+        let before = self.order.clone();
+        self.order.swap_move(from, to);
+
+        if self.order == before {
+            return;
+        }
+
+        self.undo.push_back(OrderMove { from, to });
+        self.trim();
+
+        self.redo.clear();
+        self.version += 1;
+    }
+
+    /// Undoes the last move, returning it.
+    pub fn undo(&mut self) -> Option<OrderMove> {
+        let last = self.undo.pop_back()?;
+
+        let inverse = last.inverse();
+        self.order.swap_move(inverse.from, inverse.to);
+
+        self.redo.push(last);
+        self.version += 1;
+
+        Some(last)
+    }
+
+    /// Redoes the last undone move, returning it.
+    pub fn redo(&mut self) -> Option<OrderMove> {
+        let last = self.redo.pop()?;
+
+        self.order.swap_move(last.from, last.to);
+
+        self.undo.push_back(last);
+        self.version += 1;
+
+        Some(last)
+    }
+
+    /// Forgets every recorded move, keeping the order.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.version += 1;
+    }
+
+    /// Resizes the order, see [`Order::resize_items`].
+    ///
+    /// Recorded moves don't apply to a different number of items, so they're forgotten.
+    #[inline]
+    pub fn resize(&mut self, size: usize) {
+        if self.order.len() != size {
+            self.order.resize_items(size);
+            self.clear();
+        }
+    }
+}
+
+impl Default for OrderHistory {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Order::new())
+    }
+}
+
+#[cfg(feature = "sortable")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OrderHistoryOptions {
+    /// The number of moves kept.
+    pub capacity: usize,
+    /// Undo with Ctrl+Z, and redo with Ctrl+Shift+Z or Ctrl+Y, unless typing in an input.
+    pub shortcuts: bool,
+    /// Called with the new order after an undo or redo.
+    pub onchange: Callback<Order>,
+}
+
+#[cfg(feature = "sortable")]
+impl Default for OrderHistoryOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            capacity: OrderHistory::DEFAULT_CAPACITY,
+            shortcuts: false,
+            onchange: Callback::default(),
+        }
+    }
+}
+
+/// A shared [`OrderHistory`], see [`use_order_history`].
+#[cfg(feature = "sortable")]
+#[derive(Clone)]
+pub struct UseOrderHistoryHandle {
+    history: Rc<RefCell<OrderHistory>>,
+    onchange: Rc<RefCell<Callback<Order>>>,
+    /// The version at the last render, so props holding the handle change with the history.
+    version: u64,
+    update: UseForceUpdateHandle,
+}
+
+#[cfg(feature = "sortable")]
+impl UseOrderHistoryHandle {
+    #[inline]
+    pub fn order(&self) -> Order {
+        self.history.borrow().order().clone()
+    }
+
+    #[inline]
+    pub fn can_undo(&self) -> bool {
+        self.history.borrow().can_undo()
+    }
+
+    #[inline]
+    pub fn can_redo(&self) -> bool {
+        self.history.borrow().can_redo()
+    }
+
+    /// Undoes the last move, returning true if there was one.
+    pub fn undo(&self) -> bool {
+        let undone = self.history.borrow_mut().undo().is_some();
+
+        if undone {
+            self.changed();
+        }
+
+        undone
+    }
+
+    /// Redoes the last undone move, returning true if there was one.
+    pub fn redo(&self) -> bool {
+        let redone = self.history.borrow_mut().redo().is_some();
+
+        if redone {
+            self.changed();
+        }
+
+        redone
+    }
+
+    #[inline]
+    pub fn clear(&self) {
+        self.history.borrow_mut().clear();
+        self.update.force_update();
+    }
+
+    /// Records a move, see [`OrderHistory::swap_move`].
+    #[inline]
+    pub fn swap_move(&self, from: usize, to: usize) {
+        self.history.borrow_mut().swap_move(from, to);
+        self.update.force_update();
+    }
+
+    /// Resizes the history, see [`OrderHistory::resize`].
+    ///
+    /// Forgetting the moves changes the version, so the component keeping the history is
+    /// rerendered to get an up to date handle.
+    pub(crate) fn resize(&self, size: usize) {
+        if self.history.borrow().order().len() == size {
+            return;
+        }
+
+        self.history.borrow_mut().resize(size);
+        self.update.force_update();
+    }
+
+    fn changed(&self) {
+        let order = self.order();
+        let onchange = self.onchange.borrow().clone();
+
+        self.update.force_update();
+        onchange.emit(order);
+    }
+}
+
+#[cfg(feature = "sortable")]
+impl PartialEq for UseOrderHistoryHandle {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.history, &other.history) && self.version == other.version
+    }
+}
+
+#[cfg(feature = "sortable")]
+fn is_editing(event: &KeyboardEvent) -> bool {
+    let Some(element) = event
+        .target()
+        .and_then(|target| target.dyn_into::<HtmlElement>().ok())
+    else {
+        return false;
+    };
+
+    matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+        || element.is_content_editable()
+}
+
+/// Keeps an [`OrderHistory`], to be passed to
+/// [`SortableListProps::history`](crate::SortableListProps::history).
+///
+/// The component rerenders whenever the history changes.
+#[cfg(feature = "sortable")]
+#[hook]
+pub fn use_order_history(options: OrderHistoryOptions) -> UseOrderHistoryHandle {
+    let history = use_mut_ref(|| OrderHistory::with_capacity(Order::new(), options.capacity));
+    let onchange = use_mut_ref(|| options.onchange.clone());
+    let update = use_force_update();
+
+    history.borrow_mut().set_capacity(options.capacity);
+    *onchange.borrow_mut() = options.onchange;
+
+    let version = history.borrow().version();
+    let handle = UseOrderHistoryHandle {
+        history,
+        version,
+        onchange,
+        update,
+    };
+
+    {
+        let handle = handle.clone();
+
+        use_effect_with_deps(
+            move |shortcuts| {
+                let listener = shortcuts.then(|| {
+                    let window = web_sys::window().unwrap();
+
+                    EventListener::new(&window, "keydown", move |event: KeyboardEvent| {
+                        if !(event.ctrl_key() || event.meta_key()) || is_editing(&event) {
+                            return;
+                        }
+
+                        let key = event.key().to_lowercase();
+
+                        let handled = match key.as_str() {
+                            "z" if event.shift_key() => handle.redo(),
+                            "z" => handle.undo(),
+                            "y" => handle.redo(),
+                            _ => false,
+                        };
+
+                        if handled {
+                            event.prevent_default();
+                        }
+                    })
+                });
+
+                move || drop(listener)
+            },
+            options.shortcuts,
+        );
+    }
+
+    handle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(len: usize) -> OrderHistory {
+        let mut order = Order::new();
+        order.resize(len);
+        OrderHistory::new(order)
+    }
+
+    #[test]
+    fn inverse_restores_order() {
+        for len in 1..6 {
+            for from in 0..len {
+                for to in 0..=len {
+                    let mut order = Order::new();
+                    order.resize(len);
+
+                    let inverse = OrderMove { from, to }.inverse();
+                    order.swap_move(from, to);
+                    order.swap_move(inverse.from, inverse.to);
+
+                    assert_eq!(*order, (0..len).collect::<Vec<_>>(), "{} -> {}", from, to);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let mut history = history(4);
+
+        history.swap_move(0, 4);
+        history.swap_move(3, 1);
+        assert_eq!(**history.order(), [1, 0, 2, 3]);
+
+        history.undo();
+        assert_eq!(**history.order(), [1, 2, 3, 0]);
+
+        history.undo();
+        assert_eq!(**history.order(), [0, 1, 2, 3]);
+        assert!(history.undo().is_none());
+
+        history.redo();
+        assert_eq!(**history.order(), [1, 2, 3, 0]);
+
+        // a new move forgets the undone ones
+        history.swap_move(0, 2);
+        assert!(!history.can_redo());
+        assert_eq!(**history.order(), [2, 1, 3, 0]);
+    }
+
+    #[test]
+    fn capacity_drops_oldest_moves() {
+        let mut history = OrderHistory::with_capacity(history(3).order().clone(), 2);
+
+        history.swap_move(0, 3);
+        history.swap_move(0, 3);
+        history.swap_move(0, 3);
+
+        assert!(history.undo().is_some());
+        assert!(history.undo().is_some());
+        assert!(history.undo().is_none());
+        assert_eq!(**history.order(), [1, 2, 0]);
+    }
+
+    #[test]
+    fn no_op_moves_are_not_recorded() {
+        let mut history = history(3);
+
+        history.swap_move(1, 1);
+        history.swap_move(1, 2);
+        assert!(!history.can_undo());
+    }
+}
@@ -0,0 +1,284 @@
+use std::{fmt::Display, rc::Rc, str::FromStr};
+
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::{RangeValidator, TextInput, TextInputProps, ValidationError, Validator};
+
+/// The state of an input whose text is parsed, see [`use_parsed_text`].
+struct ParsedText {
+    text: String,
+    error: Option<String>,
+    oninput: Callback<String>,
+}
+
+type Parse<T, E> = Rc<dyn Fn(&str) -> Result<T, E>>;
+
+/// Parses the text of an input on every change, emitting the result to `oninput`.
+///
+/// The text is kept while it doesn't parse, and follows `value` when the parent changes it.
+/// Errors aren't shown for an empty input.
+///
+/// Inputs like `type="number"` report an empty text while what's typed isn't valid for them,
+/// which is told apart with the validity of the input referenced by `node_ref`.
+#[hook]
+fn use_parsed_text<T, E, F>(
+    value: Option<T>,
+    parse: F,
+    oninput: Callback<Result<T, E>>,
+    node_ref: NodeRef,
+) -> ParsedText
+where
+    T: Display + PartialEq + 'static,
+    E: Display + 'static,
+    F: Fn(&str) -> Result<T, E> + 'static,
+{
+    let text = use_state_eq(|| value.as_ref().map(T::to_string).unwrap_or_default());
+    let error = use_state_eq(|| None::<String>);
+
+    let parse = Rc::new(parse) as Parse<T, E>;
+    let latest = use_mut_ref(|| parse.clone());
+    *latest.borrow_mut() = parse;
+
+    {
+        let text = text.clone();
+        let error = error.clone();
+        let latest = latest.clone();
+
+        use_effect_with_deps(
+            move |value| {
+                let parse = latest.borrow().clone();
+
+                // the text may already mean the value, like `1.0` for `1`
+                if let Some(value) = value {
+                    if parse(&text).ok().as_ref() != Some(value) {
+                        text.set(value.to_string());
+                        error.set(None);
+                    }
+                }
+            },
+            value,
+        );
+    }
+
+    let oninput = use_callback(
+        move |input: String, (text, error, oninput, node_ref)| {
+            let parse = latest.borrow().clone();
+            let result = parse(&input);
+
+            let bad_input = input.is_empty() && has_bad_input(node_ref);
+
+            error.set(match &result {
+                Err(e) if !input.is_empty() || bad_input => Some(e.to_string()),
+                _ => None,
+            });
+
+            // the typed text is only in the input, setting the empty one would erase it
+            if !bad_input {
+                text.set(input);
+            }
+
+            oninput.emit(result);
+        },
+        (text.clone(), error.clone(), oninput, node_ref),
+    );
+
+    ParsedText {
+        text: (*text).clone(),
+        error: (*error).clone(),
+        oninput,
+    }
+}
+
+fn has_bad_input(node_ref: &NodeRef) -> bool {
+    node_ref
+        .cast::<HtmlInputElement>()
+        .is_some_and(|element| element.validity().bad_input())
+}
+
+fn render_error(error: Option<&str>) -> Html {
+    match error {
+        Some(error) => html! {
+            <span class="garlic-input-error" role="alert">{ error }</span>
+        },
+        None => html! {},
+    }
+}
+
+/// Called with the result of parsing a `T`.
+pub type ParsedCallback<T> = Callback<Result<T, <T as FromStr>::Err>>;
+
+#[derive(Properties, PartialEq)]
+pub struct ParsedInputProps<T>
+where
+    T: FromStr + PartialEq + 'static,
+{
+    /// The props of the [`TextInput`], its `value` and `oninput` are replaced by the typed
+    /// ones below.
+    #[prop_or_default]
+    pub input: TextInputProps,
+    pub value: Option<T>,
+    /// Called with the parsed text on every change.
+    #[prop_or_default]
+    pub oninput: ParsedCallback<T>,
+}
+
+/// A [`TextInput`] for any [`FromStr`] type, showing the parse error under the input.
+#[function_component]
+pub fn ParsedInput<T>(props: &ParsedInputProps<T>) -> Html
+where
+    T: FromStr + Display + PartialEq + Clone + 'static,
+    T::Err: Display,
+{
+    let parsed = use_parsed_text(
+        props.value.clone(),
+        T::from_str,
+        props.oninput.clone(),
+        props.input.node_ref.clone(),
+    );
+
+    let input = TextInputProps {
+        class: classes!("garlic-parsed-input", props.input.class.clone()),
+        value: Some(parsed.text),
+        invalid: props.input.invalid || parsed.error.is_some(),
+        oninput: parsed.oninput,
+        ..props.input.clone()
+    };
+
+    html! {
+        <>
+            <TextInput ..input />
+            { render_error(parsed.error.as_deref()) }
+        </>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct NumberInputProps {
+    /// The props of the [`TextInput`], its `value` and `oninput` are replaced by the typed
+    /// ones below.
+    #[prop_or_default]
+    pub input: TextInputProps,
+    pub value: Option<f64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// The step of the arrows of the input, the number must be `min`, or `0`, plus a
+    /// multiple of it.
+    pub step: Option<f64>,
+    /// Called with the number on every change, or why it isn't valid.
+    #[prop_or_default]
+    pub oninput: Callback<Result<f64, ValidationError>>,
+}
+
+/// Parses a number, checking it's in `range` and on one of its steps.
+fn parse_number(text: &str, range: RangeValidator) -> Result<f64, ValidationError> {
+    let number = text
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| ValidationError::NotANumber)?;
+
+    range.validate(&number)?;
+    Ok(number)
+}
+
+/// A `type="number"` input, showing why the number isn't valid under the input.
+#[function_component]
+pub fn NumberInput(props: &NumberInputProps) -> Html {
+    let range = RangeValidator {
+        min: props.min,
+        max: props.max,
+        step: props.step,
+    };
+    let parsed = use_parsed_text(
+        props.value,
+        move |text| parse_number(text, range),
+        props.oninput.clone(),
+        props.input.node_ref.clone(),
+    );
+
+    let input = TextInputProps {
+        class: classes!("garlic-number-input", props.input.class.clone()),
+        value: Some(parsed.text),
+        input_type: String::from("number"),
+        min: props.min.map(|min| min.to_string()),
+        max: props.max.map(|max| max.to_string()),
+        step: props.step.map(|step| step.to_string()),
+        invalid: props.input.invalid || parsed.error.is_some(),
+        oninput: parsed.oninput,
+        ..props.input.clone()
+    };
+
+    html! {
+        <>
+            <TextInput ..input />
+            { render_error(parsed.error.as_deref()) }
+        </>
+    }
+}
+
+/// A [`TextInput`] hiding what's typed.
+#[function_component]
+pub fn PasswordInput(props: &TextInputProps) -> Html {
+    let props = TextInputProps {
+        input_type: String::from("password"),
+        ..props.clone()
+    };
+
+    html! { <TextInput ..props /> }
+}
+
+/// A [`TextInput`] for email addresses, where mobile keyboards show `@`.
+///
+/// Browsers only check the address on form submission, see [`EmailValidator`](crate::EmailValidator)
+/// to check it while typing.
+#[function_component]
+pub fn EmailInput(props: &TextInputProps) -> Html {
+    let props = TextInputProps {
+        input_type: String::from("email"),
+        ..props.clone()
+    };
+
+    html! { <TextInput ..props /> }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_number_checks_bounds() {
+        let unbounded = RangeValidator::default();
+        let range = RangeValidator::new(Some(0.0), Some(10.0));
+
+        assert_eq!(parse_number(" 2.5 ", unbounded), Ok(2.5));
+        assert_eq!(
+            parse_number("-1", range),
+            Err(ValidationError::BelowMin(0.0))
+        );
+        assert_eq!(
+            parse_number("11", range),
+            Err(ValidationError::AboveMax(10.0))
+        );
+        assert_eq!(
+            parse_number("ten", unbounded),
+            Err(ValidationError::NotANumber)
+        );
+
+        let stepped = RangeValidator::new(Some(0.5), None).with_step(0.25);
+        assert_eq!(parse_number("1.25", stepped), Ok(1.25));
+        assert_eq!(parse_number("0.5", stepped), Ok(0.5));
+        assert_eq!(
+            parse_number("1.1", stepped),
+            Err(ValidationError::StepMismatch {
+                base: 0.5,
+                step: 0.25
+            })
+        );
+
+        assert_eq!(parse_number("NaN", range), Err(ValidationError::NotANumber));
+        assert_eq!(
+            parse_number("inf", unbounded),
+            Err(ValidationError::NotANumber)
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+/// The digits of a rank, in lexicographic order.
+const DIGITS: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const BASE: u8 = DIGITS.len() as u8;
+
+/// An error produced when a rank can't be placed between two others.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RankError {
+    /// The rank has a character that isn't a digit.
+    InvalidDigit(char),
+    /// The rank is empty or ends with `0`, so no rank can always be placed before it.
+    InvalidEnd,
+    /// The ranks are equal or in the wrong order.
+    NotOrdered,
+}
+
+impl Display for RankError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RankError::InvalidDigit(c) => write!(f, "invalid rank digit {:?}", c),
+            RankError::InvalidEnd => write!(f, "rank is empty or ends with `0`"),
+            RankError::NotOrdered => write!(f, "ranks are not in order"),
+        }
+    }
+}
+
+impl Error for RankError {}
+
+fn parse_rank(rank: &str) -> Result<Vec<u8>, RankError> {
+    let digits = rank
+        .chars()
+        .map(|c| match c {
+            '0'..='9' => Ok(c as u8 - b'0'),
+            'A'..='Z' => Ok(c as u8 - b'A' + 10),
+            'a'..='z' => Ok(c as u8 - b'a' + 36),
+            c => Err(RankError::InvalidDigit(c)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match digits.last() {
+        None | Some(0) => Err(RankError::InvalidEnd),
+        Some(_) => Ok(digits),
+    }
+}
+
+fn format_rank(digits: &[u8]) -> String {
+    digits.iter().map(|&d| DIGITS[d as usize] as char).collect()
+}
+
+/// Gets the digits between `a` and `b`, where a missing `b` is after every rank.
+///
+/// `a` is padded with zeros, and must be before `b`.
+fn midpoint(a: &[u8], b: Option<&[u8]>) -> Vec<u8> {
+    if let Some(b) = b {
+        let common = b
+            .iter()
+            .enumerate()
+            .take_while(|&(i, &digit)| a.get(i).copied().unwrap_or(0) == digit)
+            .count();
+
+        if common > 0 {
+            let mut digits = b[..common].to_vec();
+            digits.extend(midpoint(a.get(common..).unwrap_or(&[]), Some(&b[common..])));
+            return digits;
+        }
+    }
+
+    let low = a.first().copied().unwrap_or(0);
+    let high = b.map_or(BASE, |b| b[0]);
+
+    if high - low > 1 {
+        return vec![(low + high) / 2];
+    }
+
+    match b {
+        // the first digit of `b` alone is before `b` and after `a`
+        Some(b) if b.len() > 1 => vec![b[0]],
+        _ => {
+            let mut digits = vec![low];
+            digits.extend(midpoint(a.get(1..).unwrap_or(&[]), None));
+            digits
+        }
+    }
+}
+
+/// Gets a rank that sorts between `before` and `after`, for fractional indexing.
+///
+/// Ranks are strings of `0-9`, `A-Z` and `a-z` compared lexicographically, which
+/// lets an item be moved by giving it a new rank, without renumbering the others.
+/// `None` stands for the start or end of the list.
+pub fn rank_between(before: Option<&str>, after: Option<&str>) -> Result<String, RankError> {
+    let before = before.map(parse_rank).transpose()?;
+    let after = after.map(parse_rank).transpose()?;
+
+    if let (Some(before), Some(after)) = (&before, &after) {
+        if before >= after {
+            return Err(RankError::NotOrdered);
+        }
+    }
+
+    let digits = midpoint(before.as_deref().unwrap_or(&[]), after.as_deref());
+    Ok(format_rank(&digits))
+}
+
+/// Gets `count` evenly spaced ranks, in order.
+pub fn initial_ranks(count: usize) -> Vec<String> {
+    // enough digits to fit `count` ranks with room between them
+    let mut len = 1;
+    let mut space = BASE as u128;
+    while space <= count as u128 * 2 && len < 20 {
+        len += 1;
+        space *= BASE as u128;
+    }
+
+    let step = space / (count as u128 + 1);
+
+    (1..=count as u128)
+        .map(|i| {
+            let mut value = i * step;
+            let mut digits = vec![0; len];
+
+            for digit in digits.iter_mut().rev() {
+                *digit = (value % BASE as u128) as u8;
+                value /= BASE as u128;
+            }
+
+            while digits.last() == Some(&0) {
+                digits.pop();
+            }
+
+            format_rank(&digits)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rank_between_neighbours() {
+        assert_eq!(rank_between(None, None).unwrap(), "V");
+        assert_eq!(rank_between(Some("V"), None).unwrap(), "k");
+        assert_eq!(rank_between(None, Some("V")).unwrap(), "F");
+        assert_eq!(rank_between(Some("a"), Some("c")).unwrap(), "b");
+        assert_eq!(rank_between(Some("a"), Some("b")).unwrap(), "aV");
+        assert_eq!(rank_between(Some("a"), Some("b1")).unwrap(), "b");
+        assert_eq!(rank_between(Some("z"), None).unwrap(), "zV");
+        assert_eq!(rank_between(None, Some("01")).unwrap(), "00V");
+    }
+
+    #[test]
+    fn rank_between_rejects_invalid_ranks() {
+        assert_eq!(
+            rank_between(Some("b"), Some("a")),
+            Err(RankError::NotOrdered)
+        );
+        assert_eq!(
+            rank_between(Some("a"), Some("a")),
+            Err(RankError::NotOrdered)
+        );
+        assert_eq!(
+            rank_between(Some("a-"), None),
+            Err(RankError::InvalidDigit('-'))
+        );
+        assert_eq!(rank_between(Some("a0"), None), Err(RankError::InvalidEnd));
+        assert_eq!(rank_between(Some(""), None), Err(RankError::InvalidEnd));
+    }
+
+    #[test]
+    fn repeated_inserts_stay_ordered() {
+        let mut ranks = initial_ranks(3);
+
+        for i in 0..200 {
+            // insert at the front, the back and next to the previous insert
+            let index = [0, ranks.len(), i % (ranks.len() + 1)][i % 3];
+
+            let before = index.checked_sub(1).map(|i| ranks[i].as_str());
+            let after = ranks.get(index).map(String::as_str);
+
+            let rank = rank_between(before, after).unwrap();
+            ranks.insert(index, rank);
+        }
+
+        assert!(ranks.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn initial_ranks_are_ordered() {
+        assert!(initial_ranks(0).is_empty());
+
+        for count in [1, 10, 61, 62, 1000] {
+            let ranks = initial_ranks(count);
+
+            assert_eq!(ranks.len(), count);
+            assert!(ranks.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(ranks.iter().all(|rank| parse_rank(rank).is_ok()));
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use js_sys::Array;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{
+    Element, ResizeObserver, ResizeObserverBoxOptions, ResizeObserverEntry, ResizeObserverOptions,
+    ResizeObserverSize,
+};
+use yew::Callback;
+
+use crate::Interval;
+
+/// The box of an element that is observed for size changes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ResizeBox {
+    /// The content box, excluding padding and border.
+    #[default]
+    ContentBox,
+    /// The border box, including padding and border.
+    BorderBox,
+}
+
+impl ResizeBox {
+    #[inline]
+    fn options(&self) -> ResizeObserverOptions {
+        let options = ResizeObserverOptions::new();
+
+        match self {
+            ResizeBox::ContentBox => options.set_box(ResizeObserverBoxOptions::ContentBox),
+            ResizeBox::BorderBox => options.set_box(ResizeObserverBoxOptions::BorderBox),
+        }
+
+        options
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Size {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Size {
+    #[inline]
+    pub const fn new(width: f32, height: f32) -> Self {
+        Self { width, height }
+    }
+
+    /// Measures the bounding rect of `element`.
+    #[inline]
+    pub fn of(element: &Element) -> Self {
+        let rect = element.get_bounding_client_rect();
+        Self::new(rect.width() as f32, rect.height() as f32)
+    }
+
+    fn from_entry(entry: &ResizeObserverEntry, resize_box: ResizeBox) -> Self {
+        let sizes = match resize_box {
+            ResizeBox::ContentBox => entry.content_box_size(),
+            ResizeBox::BorderBox => entry.border_box_size(),
+        };
+
+        // older browsers report a single size instead of an array, or no sizes at all
+        let size: JsValue = if Array::is_array(&sizes) {
+            sizes.get(0)
+        } else {
+            sizes.into()
+        };
+
+        if !size.is_undefined() {
+            let size: ResizeObserverSize = size.unchecked_into();
+            return Self::new(size.inline_size() as f32, size.block_size() as f32);
+        }
+
+        match resize_box {
+            ResizeBox::ContentBox => {
+                let rect = entry.content_rect();
+                Self::new(rect.width() as f32, rect.height() as f32)
+            }
+            ResizeBox::BorderBox => Self::of(&entry.target()),
+        }
+    }
+}
+
+struct Observation {
+    id: usize,
+    element: Element,
+    size: Option<Size>,
+    callback: Callback<Size>,
+}
+
+enum Backend {
+    Observer {
+        observer: ResizeObserver,
+        _closure: Closure<dyn FnMut(Array)>,
+    },
+    /// Used when `ResizeObserver` isn't supported, polls every observed element.
+    Polling { interval: RefCell<Option<Interval>> },
+}
+
+/// A single resize observer shared by every [`ResizeObservation`] of a [`ResizeBox`].
+///
+/// This way callbacks for many elements are batched into a single notification.
+struct SharedResizeObserver {
+    resize_box: ResizeBox,
+    observations: RefCell<Vec<Observation>>,
+    backend: Backend,
+}
+
+/// How often elements are polled when `ResizeObserver` isn't supported.
+const POLL_INTERVAL_MS: u32 = 50;
+
+thread_local! {
+    static CONTENT_BOX: Rc<SharedResizeObserver> =
+        Rc::new(SharedResizeObserver::new(ResizeBox::ContentBox));
+    static BORDER_BOX: Rc<SharedResizeObserver> =
+        Rc::new(SharedResizeObserver::new(ResizeBox::BorderBox));
+    static NEXT_ID: Cell<usize> = const { Cell::new(0) };
+}
+
+fn supports_resize_observer() -> bool {
+    let window = web_sys::window().unwrap();
+    js_sys::Reflect::has(&window, &JsValue::from_str("ResizeObserver")).unwrap_or(false)
+}
+
+impl SharedResizeObserver {
+    fn new(resize_box: ResizeBox) -> Self {
+        let backend = if supports_resize_observer() {
+            let closure = Closure::<dyn FnMut(Array)>::new(move |entries: Array| {
+                Self::get(resize_box).notify(entries);
+            });
+
+            let observer = ResizeObserver::new(closure.as_ref().unchecked_ref())
+                .expect("garlic failed to create resize observer");
+
+            Backend::Observer {
+                observer,
+                _closure: closure,
+            }
+        } else {
+            Backend::Polling {
+                interval: RefCell::new(None),
+            }
+        };
+
+        Self {
+            resize_box,
+            observations: RefCell::new(Vec::new()),
+            backend,
+        }
+    }
+
+    fn get(resize_box: ResizeBox) -> Rc<Self> {
+        match resize_box {
+            ResizeBox::ContentBox => CONTENT_BOX.with(Rc::clone),
+            ResizeBox::BorderBox => BORDER_BOX.with(Rc::clone),
+        }
+    }
+
+    fn observe(&self, element: &Element, callback: Callback<Size>) -> usize {
+        let id = NEXT_ID.with(|next| next.replace(next.get() + 1));
+
+        self.observations.borrow_mut().push(Observation {
+            id,
+            element: element.clone(),
+            size: None,
+            callback,
+        });
+
+        match &self.backend {
+            Backend::Observer { observer, .. } => {
+                observer.observe_with_options(element, &self.resize_box.options());
+            }
+            Backend::Polling { interval } => {
+                let resize_box = self.resize_box;
+
+                interval.borrow_mut().get_or_insert_with(|| {
+                    Interval::new(POLL_INTERVAL_MS, move || Self::get(resize_box).poll())
+                });
+            }
+        }
+
+        id
+    }
+
+    fn unobserve(&self, id: usize) {
+        let mut observations = self.observations.borrow_mut();
+
+        let Some(index) = observations.iter().position(|o| o.id == id) else {
+            return;
+        };
+
+        let observation = observations.remove(index);
+
+        match &self.backend {
+            Backend::Observer { observer, .. } => {
+                if !observations
+                    .iter()
+                    .any(|o| o.element == observation.element)
+                {
+                    observer.unobserve(&observation.element);
+                }
+            }
+            Backend::Polling { interval } => {
+                if observations.is_empty() {
+                    interval.borrow_mut().take();
+                }
+            }
+        }
+    }
+
+    /// Updates the size of every observation of `element`, collecting the callbacks to emit.
+    fn update(
+        observations: &mut [Observation],
+        element: &Element,
+        size: Size,
+        changed: &mut Vec<(Callback<Size>, Size)>,
+    ) {
+        for observation in observations.iter_mut() {
+            if observation.element == *element && observation.size != Some(size) {
+                observation.size = Some(size);
+                changed.push((observation.callback.clone(), size));
+            }
+        }
+    }
+
+    fn notify(&self, entries: Array) {
+        let mut changed = Vec::new();
+
+        {
+            let mut observations = self.observations.borrow_mut();
+
+            for entry in entries.iter() {
+                let entry: ResizeObserverEntry = entry.unchecked_into();
+                let size = Size::from_entry(&entry, self.resize_box);
+
+                Self::update(&mut observations, &entry.target(), size, &mut changed);
+            }
+        }
+
+        // callbacks are emitted after the borrow is released, since they may observe new elements
+        for (callback, size) in changed {
+            callback.emit(size);
+        }
+    }
+
+    fn poll(&self) {
+        let mut changed = Vec::new();
+
+        {
+            let mut observations = self.observations.borrow_mut();
+
+            for i in 0..observations.len() {
+                let element = observations[i].element.clone();
+                let size = Size::of(&element);
+
+                Self::update(&mut observations, &element, size, &mut changed);
+            }
+        }
+
+        for (callback, size) in changed {
+            callback.emit(size);
+        }
+    }
+}
+
+/// Observes the size of an element, calling a callback whenever it changes.
+///
+/// Uses a `ResizeObserver` shared by every observation when supported,
+/// otherwise falls back to polling the bounding rect of each element,
+/// in which case the [`ResizeBox`] is ignored.
+///
+/// The element is no longer observed when dropped.
+#[derive(Debug, PartialEq)]
+pub struct ResizeObservation {
+    id: usize,
+    resize_box: ResizeBox,
+}
+
+impl ResizeObservation {
+    pub fn new(element: &Element, resize_box: ResizeBox, callback: Callback<Size>) -> Self {
+        let id = SharedResizeObserver::get(resize_box).observe(element, callback);
+
+        Self { id, resize_box }
+    }
+}
+
+impl Drop for ResizeObservation {
+    fn drop(&mut self) {
+        SharedResizeObserver::get(self.resize_box).unobserve(self.id);
+    }
+}
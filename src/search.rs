@@ -0,0 +1,498 @@
+use std::ops::Range;
+
+#[cfg(feature = "input")]
+use std::rc::Rc;
+
+#[cfg(feature = "input")]
+use yew::prelude::*;
+
+/// A successful match of a query in a text.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Match {
+    /// How well the query matched, from `0.0` to `1.0`, higher is better.
+    pub score: f32,
+    /// The matched byte ranges of the text, sorted and not overlapping.
+    pub ranges: Vec<Range<usize>>,
+}
+
+/// Matches search queries against texts.
+pub trait Matcher {
+    /// Matches `query` against `text`, returning `None` if it doesn't match.
+    ///
+    /// An empty query matches every text, with a score of zero and no ranges.
+    fn find(&self, query: &str, text: &str) -> Option<Match>;
+}
+
+/// Matches texts containing the query exactly.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SubstringMatcher;
+
+/// Matches texts containing the query, ignoring case and diacritics, so `"cafe"` matches `"Café"`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InsensitiveMatcher;
+
+/// Matches texts containing every whitespace separated word of the query, in any order,
+/// ignoring case and diacritics.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TokenMatcher;
+
+/// Matches texts containing the characters of the query in order, ignoring case and diacritics,
+/// so `"sl"` matches `"SortableList"`.
+///
+/// Consecutive characters and characters at the start of words score higher.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FuzzyMatcher;
+
+/// Scores a substring match, earlier and tighter matches score higher.
+fn substring_score(start: usize, len: usize, text_len: usize) -> f32 {
+    let coverage = len as f32 / text_len.max(1) as f32;
+    coverage * 0.5 + 0.5 / (1.0 + start as f32)
+}
+
+impl Matcher for SubstringMatcher {
+    fn find(&self, query: &str, text: &str) -> Option<Match> {
+        if query.is_empty() {
+            return Some(Match::default());
+        }
+
+        let start = text.find(query)?;
+        let end = start + query.len();
+
+        Some(Match {
+            score: substring_score(start, query.len(), text.len()),
+            ranges: vec![Range { start, end }],
+        })
+    }
+}
+
+/// Removes the diacritics from a lowercase latin letter.
+fn strip_diacritic(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => 'a',
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => 'c',
+        'ď' | 'đ' => 'd',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => 'e',
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => 'g',
+        'ĥ' | 'ħ' => 'h',
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => 'i',
+        'ĵ' => 'j',
+        'ķ' => 'k',
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => 'l',
+        'ñ' | 'ń' | 'ņ' | 'ň' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => 'o',
+        'ŕ' | 'ŗ' | 'ř' => 'r',
+        'ś' | 'ŝ' | 'ş' | 'š' => 's',
+        'ţ' | 'ť' | 'ŧ' => 't',
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => 'u',
+        'ŵ' => 'w',
+        'ý' | 'ÿ' | 'ŷ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        c => c,
+    }
+}
+
+/// A text folded to lowercase without diacritics, keeping where each character came from.
+struct Folded {
+    chars: Vec<char>,
+    /// The byte range of the original character of each folded character.
+    spans: Vec<Range<usize>>,
+}
+
+impl Folded {
+    fn new(text: &str) -> Self {
+        let mut chars = Vec::with_capacity(text.len());
+        let mut spans = Vec::with_capacity(text.len());
+
+        for (start, c) in text.char_indices() {
+            for lower in c.to_lowercase() {
+                chars.push(strip_diacritic(lower));
+                spans.push(start..start + c.len_utf8());
+            }
+        }
+
+        Self { chars, spans }
+    }
+
+    fn find(&self, query: &[char]) -> Option<usize> {
+        if query.len() > self.chars.len() {
+            return None;
+        }
+
+        (0..=self.chars.len() - query.len()).find(|&i| self.chars[i..i + query.len()] == *query)
+    }
+
+    /// Gets the byte ranges of the folded characters at `indices`, merging neighbours.
+    fn ranges(&self, indices: impl IntoIterator<Item = usize>) -> Vec<Range<usize>> {
+        let mut spans = indices
+            .into_iter()
+            .map(|i| self.spans[i].clone())
+            .collect::<Vec<_>>();
+        spans.sort_by_key(|span| span.start);
+
+        let mut ranges: Vec<Range<usize>> = Vec::with_capacity(spans.len());
+        for span in spans {
+            match ranges.last_mut() {
+                Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+                _ => ranges.push(span),
+            }
+        }
+
+        ranges
+    }
+}
+
+fn fold_query(query: &str) -> Vec<char> {
+    Folded::new(query).chars
+}
+
+fn insensitive_find(query: &[char], text: &Folded) -> Option<Match> {
+    let start = text.find(query)?;
+
+    Some(Match {
+        score: substring_score(start, query.len(), text.chars.len()),
+        ranges: text.ranges(start..start + query.len()),
+    })
+}
+
+impl Matcher for InsensitiveMatcher {
+    fn find(&self, query: &str, text: &str) -> Option<Match> {
+        if query.is_empty() {
+            return Some(Match::default());
+        }
+
+        insensitive_find(&fold_query(query), &Folded::new(text))
+    }
+}
+
+impl Matcher for TokenMatcher {
+    fn find(&self, query: &str, text: &str) -> Option<Match> {
+        let text = Folded::new(text);
+
+        let mut score = 0.0;
+        let mut indices = Vec::new();
+        let mut tokens = 0;
+
+        for token in query.split_whitespace() {
+            let token = fold_query(token);
+            let start = text.find(&token)?;
+
+            score += substring_score(start, token.len(), text.chars.len());
+            indices.extend(start..start + token.len());
+            tokens += 1;
+        }
+
+        if tokens == 0 {
+            return Some(Match::default());
+        }
+
+        Some(Match {
+            score: score / tokens as f32,
+            ranges: text.ranges(indices),
+        })
+    }
+}
+
+/// The score of every matched character.
+const FUZZY_MATCH: f32 = 1.0;
+/// The bonus for a character right after the previous match.
+const FUZZY_CONSECUTIVE: f32 = 3.0;
+/// The bonus for a character at the start of a word.
+const FUZZY_WORD_START: f32 = 2.0;
+/// The penalty for every skipped character between two matches.
+const FUZZY_GAP: f32 = 0.1;
+
+fn is_word_start(text: &str, folded: &Folded, index: usize) -> bool {
+    if index == 0 {
+        return true;
+    }
+
+    let previous = folded.chars[index - 1];
+    if !previous.is_alphanumeric() {
+        return true;
+    }
+
+    // camel case humps, using the original characters
+    let current = text[folded.spans[index].clone()].chars().next();
+    let previous = text[folded.spans[index - 1].clone()].chars().next();
+
+    matches!((previous, current), (Some(p), Some(c)) if p.is_lowercase() && c.is_uppercase())
+}
+
+impl Matcher for FuzzyMatcher {
+    fn find(&self, query: &str, text: &str) -> Option<Match> {
+        let query = fold_query(query);
+        if query.is_empty() {
+            return Some(Match::default());
+        }
+
+        let folded = Folded::new(text);
+        let n = folded.chars.len();
+        let m = query.len();
+
+        if m > n {
+            return None;
+        }
+
+        let word_start = (0..n)
+            .map(|j| is_word_start(text, &folded, j))
+            .collect::<Vec<_>>();
+
+        let gain = |j: usize| FUZZY_MATCH + if word_start[j] { FUZZY_WORD_START } else { 0.0 };
+
+        // `best[i][j]` is the best score matching `query[..=i]` with `query[i]` at `j`,
+        // `from[i][j]` is where `query[i - 1]` was matched for that score
+        let mut best = vec![vec![f32::NEG_INFINITY; n]; m];
+        let mut from = vec![vec![0; n]; m];
+
+        for (j, &c) in folded.chars.iter().enumerate() {
+            if c == query[0] {
+                best[0][j] = gain(j) - FUZZY_GAP * j as f32;
+            }
+        }
+
+        for i in 1..m {
+            // the best previous match with its gap penalty, excluding the one right before `j`
+            let mut running = f32::NEG_INFINITY;
+            let mut running_from = 0;
+
+            for j in 1..n {
+                if j >= 2 {
+                    let k = j - 2;
+                    let value = best[i - 1][k] + FUZZY_GAP * k as f32;
+
+                    if value > running {
+                        running = value;
+                        running_from = k;
+                    }
+                }
+
+                if folded.chars[j] != query[i] {
+                    continue;
+                }
+
+                let gapped = running - FUZZY_GAP * (j - 1) as f32;
+                let consecutive = best[i - 1][j - 1] + FUZZY_CONSECUTIVE;
+
+                let (score, previous) = if consecutive >= gapped {
+                    (consecutive, j - 1)
+                } else {
+                    (gapped, running_from)
+                };
+
+                if score > f32::NEG_INFINITY {
+                    best[i][j] = score + gain(j);
+                    from[i][j] = previous;
+                }
+            }
+        }
+
+        let (mut j, score) = best[m - 1]
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, score)| *score > f32::NEG_INFINITY)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+
+        let mut indices = vec![0; m];
+        for i in (0..m).rev() {
+            indices[i] = j;
+            j = from[i][j];
+        }
+
+        let max = m as f32 * (FUZZY_MATCH + FUZZY_WORD_START + FUZZY_CONSECUTIVE);
+
+        Some(Match {
+            score: (score / max).clamp(0.0, 1.0),
+            ranges: folded.ranges(indices),
+        })
+    }
+}
+
+/// An item matching a search, see [`search`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchResult {
+    /// The index of the item in the searched items.
+    pub index: usize,
+    /// The text of the item that was matched.
+    pub text: String,
+    pub score: f32,
+    /// The matched byte ranges of `text`.
+    pub ranges: Vec<Range<usize>>,
+}
+
+/// Searches `items` for `query`, returning the matches from best to worst.
+///
+/// An empty query returns every item in order.
+pub fn search<T, S>(
+    items: &[T],
+    query: &str,
+    key_fn: impl Fn(&T) -> S,
+    matcher: &(impl Matcher + ?Sized),
+) -> Vec<SearchResult>
+where
+    S: Into<String>,
+{
+    let mut results = items
+        .iter()
+        .enumerate()
+        .filter_map(|(index, item)| {
+            let text = key_fn(item).into();
+            let found = matcher.find(query, &text)?;
+
+            Some(SearchResult {
+                index,
+                text,
+                score: found.score,
+                ranges: found.ranges,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // the sort is stable, so equal scores keep the order of the items
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    results
+}
+
+/// The state of [`use_search`].
+#[cfg(feature = "input")]
+#[derive(Clone, PartialEq)]
+pub struct UseSearchHandle {
+    /// The query, can be passed to [`SearchQuery`](crate::SearchQuery).
+    pub query: UseStateHandle<String>,
+    pub results: Rc<Vec<SearchResult>>,
+}
+
+/// Searches `items` for a query with a [`FuzzyMatcher`], see [`use_search_with`].
+#[cfg(feature = "input")]
+#[hook]
+pub fn use_search<T, F, S>(items: Rc<Vec<T>>, key_fn: F) -> UseSearchHandle
+where
+    T: PartialEq + 'static,
+    F: Fn(&T) -> S + 'static,
+    S: Into<String>,
+{
+    use_search_with(items, key_fn, FuzzyMatcher)
+}
+
+/// Searches `items` for a query, using `key_fn` to get the text of every item.
+///
+/// The results are only updated when the items or the query change.
+#[cfg(feature = "input")]
+#[hook]
+pub fn use_search_with<T, F, S, M>(items: Rc<Vec<T>>, key_fn: F, matcher: M) -> UseSearchHandle
+where
+    T: PartialEq + 'static,
+    F: Fn(&T) -> S + 'static,
+    S: Into<String>,
+    M: Matcher + 'static,
+{
+    let query = use_state(String::new);
+
+    let results = use_memo(
+        move |(items, query)| search(items, query, key_fn, &matcher),
+        (items, (*query).clone()),
+    );
+
+    UseSearchHandle { query, results }
+}
+
+#[cfg(feature = "input")]
+#[derive(Properties, PartialEq)]
+pub struct HighlightedTextProps {
+    #[prop_or_default]
+    pub class: Classes,
+    pub text: AttrValue,
+    /// The byte ranges to highlight, sorted and not overlapping.
+    #[prop_or_default]
+    pub ranges: Vec<Range<usize>>,
+}
+
+/// Renders `text` with the matched `ranges` in `<mark class="garlic-highlight">`.
+#[cfg(feature = "input")]
+#[function_component]
+pub fn HighlightedText(props: &HighlightedTextProps) -> Html {
+    let text = props.text.as_str();
+
+    let mut parts = Vec::with_capacity(props.ranges.len() * 2 + 1);
+    let mut end = 0;
+
+    for range in &props.ranges {
+        // ranges from another text are ignored rather than panicking
+        let Some(marked) = text.get(range.clone()) else {
+            continue;
+        };
+
+        if range.start < end {
+            continue;
+        }
+
+        parts.push(html! { { &text[end..range.start] } });
+        parts.push(html! { <mark class="garlic-highlight">{ marked }</mark> });
+        end = range.end;
+    }
+
+    parts.push(html! { { &text[end..] } });
+
+    html! {
+        <span class={ classes!("garlic-highlighted-text", props.class.clone()) }>
+            { for parts }
+        </span>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substring_is_exact() {
+        let found = SubstringMatcher.find("List", "SortableList").unwrap();
+        assert_eq!(found.ranges, [Range { start: 8, end: 12 }]);
+
+        assert!(SubstringMatcher.find("list", "SortableList").is_none());
+        assert_eq!(SubstringMatcher.find("", "a"), Some(Match::default()));
+    }
+
+    #[test]
+    fn insensitive_ignores_case_and_diacritics() {
+        let found = InsensitiveMatcher.find("CAFE", "un café").unwrap();
+
+        // `é` is two bytes
+        assert_eq!(found.ranges, [Range { start: 3, end: 8 }]);
+        assert!(InsensitiveMatcher.find("Crème", "CREME brûlée").is_some());
+        assert!(InsensitiveMatcher.find("tea", "café").is_none());
+    }
+
+    #[test]
+    fn tokens_match_in_any_order() {
+        let found = TokenMatcher.find("list sort", "Sortable list").unwrap();
+        assert_eq!(found.ranges, [0..4, 9..13]);
+
+        assert!(TokenMatcher.find("list drag", "Sortable list").is_none());
+    }
+
+    #[test]
+    fn fuzzy_prefers_word_starts_and_runs() {
+        let found = FuzzyMatcher.find("sl", "SortableList").unwrap();
+        assert_eq!(found.ranges, [0..1, 8..9]);
+
+        let found = FuzzyMatcher.find("list", "a little list").unwrap();
+        assert_eq!(found.ranges, [Range { start: 9, end: 13 }]);
+
+        assert!(FuzzyMatcher.find("lz", "SortableList").is_none());
+        assert!(FuzzyMatcher.find("long query", "short").is_none());
+    }
+
+    #[test]
+    fn search_ranks_results() {
+        let items = ["Draggable", "DragArea", "SortableList", "Droppable"];
+
+        let results = search(&items, "dra", |item| *item, &FuzzyMatcher);
+        let found = results.iter().map(|r| r.index).collect::<Vec<_>>();
+        assert_eq!(found, [0, 1, 3]);
+
+        let results = search(&items, "", |item| *item, &FuzzyMatcher);
+        assert_eq!(results.len(), items.len());
+        assert!(results.iter().enumerate().all(|(i, r)| r.index == i));
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::VecDeque;
+
+/// Recent search queries, most recent first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchHistory {
+    queries: VecDeque<String>,
+    capacity: usize,
+}
+
+impl SearchHistory {
+    /// How many recent queries are remembered, unless a capacity is given.
+    pub const DEFAULT_CAPACITY: usize = 10;
+
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+
+    /// Creates an empty history remembering up to `capacity` queries.
+    ///
+    /// Once it's full, pushing a new query forgets the least recent one.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            queries: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    #[inline]
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.queries.truncate(capacity);
+    }
+
+    #[inline]
+    pub fn queries(&self) -> impl Iterator<Item = &str> {
+        self.queries.iter().map(String::as_str)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.queries.is_empty()
+    }
+
+    /// Records a query as the most recent, moving it up if it's already there.
+    ///
+    /// Blank queries aren't recorded. A pasted query can span several lines, which are
+    /// joined with spaces so it's saved as a single query.
+    pub fn push(&mut self, query: &str) {
+        let query = query
+            .split(['\r', '\n'])
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        if query.is_empty() {
+            return;
+        }
+
+        self.queries.retain(|q| *q != query);
+        self.queries.push_front(query);
+        self.queries.truncate(self.capacity);
+    }
+
+    #[inline]
+    pub fn remove(&mut self, query: &str) {
+        self.queries.retain(|q| q != query);
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.queries.clear();
+    }
+
+    /// Reads a history saved by [`SearchHistory::serialize`], one query per line.
+    pub fn deserialize(saved: &str, capacity: usize) -> Self {
+        let mut history = Self::with_capacity(capacity);
+
+        for query in saved.lines().rev() {
+            history.push(query);
+        }
+
+        history
+    }
+
+    /// Writes the history one query per line, [`SearchHistory::push`] removes line breaks
+    /// from the queries.
+    pub fn serialize(&self) -> String {
+        self.queries
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Loads the history saved in `localStorage` under `key`, empty if there is none.
+    #[cfg(feature = "input")]
+    pub fn load(key: &str, capacity: usize) -> Self {
+        let saved = local_storage().and_then(|storage| storage.get_item(key).ok().flatten());
+
+        match saved {
+            Some(saved) => Self::deserialize(&saved, capacity),
+            None => Self::with_capacity(capacity),
+        }
+    }
+
+    /// Saves the history in `localStorage` under `key`.
+    ///
+    /// Storage can be unavailable or full, so failing to save is ignored.
+    #[cfg(feature = "input")]
+    pub fn save(&self, key: &str) {
+        if let Some(storage) = local_storage() {
+            let _ = storage.set_item(key, &self.serialize());
+        }
+    }
+}
+
+impl Default for SearchHistory {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "input")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queries(history: &SearchHistory) -> Vec<&str> {
+        history.queries().collect()
+    }
+
+    #[test]
+    fn push_moves_recent_queries_first() {
+        let mut history = SearchHistory::with_capacity(3);
+
+        history.push("a");
+        history.push("b");
+        history.push("  ");
+        history.push(" a ");
+        assert_eq!(queries(&history), ["a", "b"]);
+
+        history.push("c");
+        history.push("d");
+        assert_eq!(queries(&history), ["d", "c", "a"]);
+    }
+
+    #[test]
+    fn serialize_round_trips() {
+        let mut history = SearchHistory::new();
+        history.push("status:open");
+        history.push("\"exact phrase\"");
+        history.push("priority:>2\r\n  -tag:wip\n");
+
+        let saved = history.serialize();
+        assert_eq!(SearchHistory::deserialize(&saved, 10), history);
+        assert_eq!(
+            queries(&SearchHistory::deserialize(&saved, 1)),
+            ["priority:>2 -tag:wip"]
+        );
+    }
+}
@@ -1,7 +1,450 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    iter::Peekable,
+    ops::Range,
+    str::{CharIndices, FromStr},
+};
+
+#[cfg(feature = "input")]
+use std::cell::RefCell;
+
+#[cfg(feature = "input")]
+use web_sys::HtmlElement;
+#[cfg(feature = "input")]
 use yew::prelude::*;
 
-use crate::TextInput;
+#[cfg(feature = "input")]
+use crate::{
+    Debounce, HighlightedText, InputAction, InsensitiveMatcher, Keymap, Matcher, SearchHistory,
+    TextInput,
+};
+
+/// A comparison of a numeric field, like `priority:>2`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    /// Compares `value` to `target`, like `value < target` for [`Comparison::Less`].
+    pub fn compare(self, value: f64, target: f64) -> bool {
+        match self {
+            Comparison::Equal => value == target,
+            Comparison::Less => value < target,
+            Comparison::LessOrEqual => value <= target,
+            Comparison::Greater => value > target,
+            Comparison::GreaterOrEqual => value >= target,
+        }
+    }
+}
+
+/// What the value of a field must be.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldFilter {
+    /// The field matches the text, like `status:open` or `title:"exact phrase"`.
+    Text(String),
+    /// The field is a number comparing to this one, like `priority:>2` or `priority:=2`.
+    Compare(Comparison, f64),
+}
+
+/// A parsed search query, see [`Query::parse`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Query {
+    /// A word, like `open`.
+    Term(String),
+    /// A quoted phrase, like `"exact phrase"`.
+    Phrase(String),
+    /// A field filter, like `status:open`.
+    Field { name: String, filter: FieldFilter },
+    /// A negated query, like `-tag:wip`.
+    Not(Box<Query>),
+    /// Queries that must all match, separated by whitespace. Empty for an empty query.
+    And(Vec<Query>),
+    /// Queries where any must match, separated by `OR` or `|`.
+    Or(Vec<Query>),
+}
+
+/// An error produced when parsing a malformed search query.
+///
+/// Spans are byte ranges of the parsed string, to show where the query is invalid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseQueryError {
+    /// A `"` without a closing one.
+    UnterminatedPhrase { span: Range<usize> },
+    /// A `(` without a `)`, or the other way around.
+    UnbalancedParenthesis { span: Range<usize> },
+    /// Parentheses with nothing in them.
+    EmptyGroup { span: Range<usize> },
+    /// A field without a value, like `status:`.
+    MissingValue { span: Range<usize> },
+    /// A comparison with something other than a number, like `priority:>high`.
+    InvalidNumber { span: Range<usize> },
+    /// A `-` with nothing to negate.
+    DanglingNegation { span: Range<usize> },
+    /// An `OR` without a query on both sides.
+    DanglingOr { span: Range<usize> },
+}
+
+impl ParseQueryError {
+    /// Describes the error, without its span.
+    pub fn message(&self) -> &'static str {
+        match self {
+            ParseQueryError::UnterminatedPhrase { .. } => "unterminated phrase",
+            ParseQueryError::UnbalancedParenthesis { .. } => "unbalanced parenthesis",
+            ParseQueryError::EmptyGroup { .. } => "empty parentheses",
+            ParseQueryError::MissingValue { .. } => "field is missing a value",
+            ParseQueryError::InvalidNumber { .. } => "comparison is not a number",
+            ParseQueryError::DanglingNegation { .. } => "nothing to negate",
+            ParseQueryError::DanglingOr { .. } => "`OR` is missing a side",
+        }
+    }
+
+    #[inline]
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            ParseQueryError::UnterminatedPhrase { span }
+            | ParseQueryError::UnbalancedParenthesis { span }
+            | ParseQueryError::EmptyGroup { span }
+            | ParseQueryError::MissingValue { span }
+            | ParseQueryError::InvalidNumber { span }
+            | ParseQueryError::DanglingNegation { span }
+            | ParseQueryError::DanglingOr { span } => span.clone(),
+        }
+    }
+}
+
+impl Display for ParseQueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let span = self.span();
+        write!(f, "{} at {}..{}", self.message(), span.start, span.end)
+    }
+}
+
+impl Error for ParseQueryError {}
+
+#[derive(Clone, Debug, PartialEq)]
+enum TokenKind {
+    Open,
+    Close,
+    Not,
+    Or,
+    Term(String),
+    Phrase(String),
+    Field { name: String, filter: FieldFilter },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    span: Range<usize>,
+}
+
+/// Splits a query into tokens.
+struct Lexer<'a> {
+    query: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(query: &'a str) -> Self {
+        Self {
+            query,
+            chars: query.char_indices().peekable(),
+        }
+    }
+
+    fn offset(&mut self) -> usize {
+        self.chars.peek().map_or(self.query.len(), |&(i, _)| i)
+    }
+
+    fn is_word_char(c: char) -> bool {
+        !(c.is_whitespace() || matches!(c, '(' | ')' | '"'))
+    }
+
+    /// Reads a phrase after its opening `"` at `start`.
+    fn phrase(&mut self, start: usize) -> Result<(String, usize), ParseQueryError> {
+        for (i, c) in self.chars.by_ref() {
+            if c == '"' {
+                return Ok((self.query[start + 1..i].to_owned(), i + 1));
+            }
+        }
+
+        Err(ParseQueryError::UnterminatedPhrase {
+            span: start..self.query.len(),
+        })
+    }
+
+    /// Reads the rest of a word, returning where it ends.
+    fn word(&mut self) -> usize {
+        while let Some(&(_, c)) = self.chars.peek() {
+            if !Self::is_word_char(c) {
+                break;
+            }
+
+            self.chars.next();
+        }
+
+        self.offset()
+    }
+
+    fn field(&mut self, start: usize, colon: usize) -> Result<Token, ParseQueryError> {
+        let name = self.query[start..colon].to_owned();
+
+        // a quoted value right after the colon, like `title:"exact phrase"`
+        if matches!(self.chars.peek(), Some((_, '"'))) {
+            let (quote, _) = self.chars.next().unwrap();
+            let (value, end) = self.phrase(quote)?;
+
+            return Ok(Token {
+                kind: TokenKind::Field {
+                    name,
+                    filter: FieldFilter::Text(value),
+                },
+                span: start..end,
+            });
+        }
+
+        let end = self.word();
+        let value = &self.query[colon + 1..end];
+
+        if value.is_empty() {
+            return Err(ParseQueryError::MissingValue { span: start..end });
+        }
+
+        let comparison = [
+            (">=", Comparison::GreaterOrEqual),
+            ("<=", Comparison::LessOrEqual),
+            (">", Comparison::Greater),
+            ("<", Comparison::Less),
+            ("=", Comparison::Equal),
+        ]
+        .into_iter()
+        .find_map(|(op, comparison)| Some((value.strip_prefix(op)?, comparison)));
+
+        let filter = match comparison {
+            Some((number, comparison)) => {
+                let number_start = end - number.len();
+
+                match number.parse() {
+                    Ok(number) => FieldFilter::Compare(comparison, number),
+                    Err(_) if number.is_empty() => {
+                        return Err(ParseQueryError::MissingValue { span: start..end });
+                    }
+                    Err(_) => {
+                        return Err(ParseQueryError::InvalidNumber {
+                            span: number_start..end,
+                        });
+                    }
+                }
+            }
+            None => FieldFilter::Text(value.to_owned()),
+        };
+
+        Ok(Token {
+            kind: TokenKind::Field { name, filter },
+            span: start..end,
+        })
+    }
+
+    fn next_token(&mut self) -> Option<Result<Token, ParseQueryError>> {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+
+        let (start, c) = self.chars.next()?;
+
+        let token = |kind, end| {
+            Ok(Token {
+                kind,
+                span: start..end,
+            })
+        };
+
+        Some(match c {
+            '(' => token(TokenKind::Open, start + 1),
+            ')' => token(TokenKind::Close, start + 1),
+            '|' => token(TokenKind::Or, start + 1),
+            '-' => token(TokenKind::Not, start + 1),
+            '"' => self
+                .phrase(start)
+                .and_then(|(phrase, end)| token(TokenKind::Phrase(phrase), end)),
+            _ => {
+                // stop at the first colon, so it can be followed by a phrase
+                while let Some(&(i, c)) = self.chars.peek() {
+                    if !Self::is_word_char(c) {
+                        break;
+                    }
+
+                    self.chars.next();
+
+                    if c == ':' && i > start {
+                        return Some(self.field(start, i));
+                    }
+                }
+
+                let end = self.offset();
+                let word = &self.query[start..end];
+
+                if word == "OR" {
+                    token(TokenKind::Or, end)
+                } else {
+                    token(TokenKind::Term(word.to_owned()), end)
+                }
+            }
+        })
+    }
+}
+
+/// Builds a [`Query`] from tokens, by recursive descent.
+struct Parser {
+    tokens: Peekable<std::vec::IntoIter<Token>>,
+}
+
+impl Parser {
+    fn or(&mut self) -> Result<Query, ParseQueryError> {
+        let mut queries = vec![];
+
+        loop {
+            let query = self.and()?;
+
+            match self.tokens.peek() {
+                Some(Token {
+                    kind: TokenKind::Or,
+                    span,
+                }) => {
+                    if query == Query::And(vec![]) {
+                        return Err(ParseQueryError::DanglingOr { span: span.clone() });
+                    }
+
+                    let span = span.clone();
+                    self.tokens.next();
+
+                    if matches!(
+                        self.tokens.peek(),
+                        None | Some(Token {
+                            kind: TokenKind::Close | TokenKind::Or,
+                            ..
+                        })
+                    ) {
+                        return Err(ParseQueryError::DanglingOr { span });
+                    }
+
+                    queries.push(query);
+                }
+                _ => {
+                    queries.push(query);
+                    break;
+                }
+            }
+        }
+
+        Ok(if queries.len() == 1 {
+            queries.pop().unwrap()
+        } else {
+            Query::Or(queries)
+        })
+    }
+
+    fn and(&mut self) -> Result<Query, ParseQueryError> {
+        let mut queries = vec![];
+
+        while let Some(token) = self.tokens.peek() {
+            if matches!(token.kind, TokenKind::Or | TokenKind::Close) {
+                break;
+            }
+
+            queries.push(self.unary()?);
+        }
+
+        Ok(if queries.len() == 1 {
+            queries.pop().unwrap()
+        } else {
+            Query::And(queries)
+        })
+    }
+
+    fn unary(&mut self) -> Result<Query, ParseQueryError> {
+        let token = self.tokens.next().unwrap();
+
+        match token.kind {
+            TokenKind::Not => match self.tokens.peek() {
+                // the `-` must be right before what it negates
+                Some(next)
+                    if next.span.start == token.span.end
+                        && !matches!(next.kind, TokenKind::Or | TokenKind::Close) =>
+                {
+                    Ok(Query::Not(Box::new(self.unary()?)))
+                }
+                _ => Err(ParseQueryError::DanglingNegation { span: token.span }),
+            },
+            TokenKind::Open => {
+                let query = self.or()?;
+
+                match self.tokens.next() {
+                    Some(Token {
+                        kind: TokenKind::Close,
+                        span,
+                    }) => {
+                        if query == Query::And(vec![]) {
+                            return Err(ParseQueryError::EmptyGroup {
+                                span: token.span.start..span.end,
+                            });
+                        }
+
+                        Ok(query)
+                    }
+                    _ => Err(ParseQueryError::UnbalancedParenthesis { span: token.span }),
+                }
+            }
+            TokenKind::Term(term) => Ok(Query::Term(term)),
+            TokenKind::Phrase(phrase) => Ok(Query::Phrase(phrase)),
+            TokenKind::Field { name, filter } => Ok(Query::Field { name, filter }),
+            TokenKind::Close | TokenKind::Or => unreachable!("stopped at by `and`"),
+        }
+    }
+}
+
+impl Query {
+    /// Parses a query like `status:open -tag:wip "exact phrase" priority:>2`.
+    ///
+    /// Words and phrases separated by whitespace must all match, `OR` or `|` match either
+    /// side and bind looser, `-` negates, and parentheses group. A field is a word
+    /// followed by `:` and a value, which may be a phrase or a comparison with a number.
+    pub fn parse(query: &str) -> Result<Self, ParseQueryError> {
+        let mut lexer = Lexer::new(query);
+        let mut tokens = vec![];
+
+        while let Some(token) = lexer.next_token() {
+            tokens.push(token?);
+        }
+
+        let mut parser = Parser {
+            tokens: tokens.into_iter().peekable(),
+        };
+
+        let query = parser.or()?;
+
+        match parser.tokens.next() {
+            // `or` only stops early at an unopened `)`
+            Some(token) => Err(ParseQueryError::UnbalancedParenthesis { span: token.span }),
+            None => Ok(query),
+        }
+    }
+}
+
+impl FromStr for Query {
+    type Err = ParseQueryError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
 
+#[cfg(feature = "input")]
 #[derive(Properties, PartialEq)]
 pub struct SearchQueryProps {
     #[prop_or_default]
@@ -15,8 +458,41 @@ pub struct SearchQueryProps {
     pub title: Option<String>,
     #[prop_or_else(|| Some(String::from("Search")))]
     pub placeholder: Option<String>,
+    /// Remembers the queries submitted with Enter in `localStorage` under this key,
+    /// and lists the recent ones under the input.
+    pub history_key: Option<String>,
+    /// The number of recent queries remembered.
+    #[prop_or(SearchHistory::DEFAULT_CAPACITY)]
+    pub history_capacity: usize,
+    /// Listed under the input after the recent queries, filtered by the query.
+    #[prop_or_default]
+    pub suggestions: Vec<String>,
+    /// Delays `oninput` and `onparse` until the query hasn't changed for this many
+    /// milliseconds, `0` emits them on every keystroke.
+    ///
+    /// The input has the `garlic-pending` class while a query is delayed.
+    #[prop_or_default]
+    pub debounce_ms: u32,
+    /// Adds the `garlic-loading` class, while the results of the query are being fetched.
+    #[prop_or_default]
+    pub loading: bool,
+    /// The shortcuts handled on `keydown`, unless `onkeydown` prevents the default.
+    ///
+    /// Defaults to [`Keymap::search`], Escape closes the suggestions before clearing the query.
+    #[prop_or_else(Keymap::search)]
+    pub keymap: Keymap,
     #[prop_or_default]
     pub oninput: Callback<String>,
+    /// Called with the query when Enter is pressed, right after any delayed `oninput`.
+    #[prop_or_default]
+    pub onsubmit: Callback<String>,
+    /// Called with the input parsed as a [`Query`], whose errors have spans to show where
+    /// the input is invalid.
+    ///
+    /// While the query doesn't parse, the input has the `garlic-invalid` class and the error
+    /// is shown under it, with the invalid part underlined.
+    #[prop_or_default]
+    pub onparse: Callback<Result<Query, ParseQueryError>>,
     #[prop_or_default]
     pub onkeypress: Callback<KeyboardEvent>,
     #[prop_or_default]
@@ -29,52 +505,603 @@ pub struct SearchQueryProps {
     pub onblur: Callback<FocusEvent>,
 }
 
+/// An entry of the dropdown under a [`SearchQuery`].
+#[cfg(feature = "input")]
+#[derive(Clone, Debug, PartialEq)]
+struct SearchOption {
+    text: String,
+    ranges: Vec<Range<usize>>,
+    /// A recent query, rather than a suggestion.
+    recent: bool,
+}
+
+/// Lists the recent queries then the suggestions containing `query`, without duplicates.
+#[cfg(feature = "input")]
+fn search_options(
+    history: &SearchHistory,
+    suggestions: &[String],
+    query: &str,
+) -> Vec<SearchOption> {
+    let recent = history.queries().map(|text| (text, true));
+    let suggested = suggestions.iter().map(|text| (text.as_str(), false));
+
+    let mut options: Vec<SearchOption> = Vec::new();
+
+    for (text, recent) in recent.chain(suggested) {
+        if text == query || options.iter().any(|option| option.text == text) {
+            continue;
+        }
+
+        if let Some(found) = InsensitiveMatcher.find(query, text) {
+            options.push(SearchOption {
+                text: text.to_owned(),
+                ranges: found.ranges,
+                recent,
+            });
+        }
+    }
+
+    options
+}
+
+/// Emits the query of a [`SearchQuery`], delaying what's typed by `ms` milliseconds.
+#[cfg(feature = "input")]
+struct QueryEmitter {
+    ms: u32,
+    emit: Callback<String>,
+    debounce: Debounce<String>,
+    /// The last query typed, to tell it apart from the parent changing the query.
+    last_typed: RefCell<String>,
+}
+
+#[cfg(feature = "input")]
+impl QueryEmitter {
+    fn new(ms: u32, emit: Callback<String>) -> Self {
+        Self {
+            ms,
+            debounce: Debounce::new(ms, emit.clone()),
+            emit,
+            last_typed: RefCell::new(String::new()),
+        }
+    }
+
+    /// Emits a typed query, returning true if it's delayed.
+    fn input(&self, query: String) -> bool {
+        *self.last_typed.borrow_mut() = query.clone();
+
+        if self.ms == 0 {
+            self.emit.emit(query);
+            return false;
+        }
+
+        self.debounce.call(query);
+        true
+    }
+
+    /// Emits `query` right away, cancelling a delayed one.
+    fn emit(&self, query: String) {
+        self.debounce.cancel();
+        self.emit.emit(query);
+    }
+
+    /// Emits the delayed query right away, nothing is emitted if there's none.
+    fn flush(&self) {
+        if self.debounce.is_pending() {
+            let query = self.last_typed.borrow().clone();
+            self.emit(query);
+        }
+    }
+
+    /// Cancels the delayed query if the parent changed the query to `query`,
+    /// returning true if it was cancelled.
+    fn sync(&self, query: &str) -> bool {
+        if *self.last_typed.borrow() == query || !self.debounce.is_pending() {
+            return false;
+        }
+
+        self.debounce.cancel();
+        true
+    }
+}
+
+#[cfg(feature = "input")]
 #[function_component]
 pub fn SearchQuery(props: &SearchQueryProps) -> Html {
-    let oninput = use_callback(
-        |event: String, (query, oninput)| {
-            if let Some(query) = query {
-                query.set(event.clone());
-            }
+    // the query when it isn't kept by the parent
+    let typed = use_state_eq(String::new);
+    let text = props.query.as_ref().unwrap_or(&typed);
+
+    let history = use_state_eq(SearchHistory::new);
+    let open = use_state_eq(|| false);
+    let active = use_state_eq(|| None::<usize>);
+    let pending = use_state_eq(|| false);
+    // the last emitted query, if it doesn't parse
+    let invalid = use_state_eq(|| None::<(String, ParseQueryError)>);
+
+    {
+        let history = history.clone();
+
+        use_effect_with_deps(
+            move |(key, capacity)| {
+                if let Some(key) = key {
+                    history.set(SearchHistory::load(key, *capacity));
+                }
+            },
+            (props.history_key.clone(), props.history_capacity),
+        );
+    }
+
+    let options = use_memo(
+        |(history, suggestions, text)| search_options(history, suggestions, text),
+        (
+            (*history).clone(),
+            props.suggestions.clone(),
+            (**text).clone(),
+        ),
+    );
+
+    let emit = use_callback(
+        |query: String, (oninput, onparse, pending, invalid)| {
+            pending.set(false);
+
+            let parsed = Query::parse(&query);
+            invalid.set(parsed.as_ref().err().map(|e| (query.clone(), e.clone())));
+
+            onparse.emit(parsed);
+            oninput.emit(query);
+        },
+        (
+            props.oninput.clone(),
+            props.onparse.clone(),
+            pending.clone(),
+            invalid.clone(),
+        ),
+    );
 
-            oninput.emit(event);
+    let latest_emit = use_mut_ref(|| emit.clone());
+    *latest_emit.borrow_mut() = emit;
+
+    let emitter = use_memo(
+        |ms| {
+            let emit = Callback::from(move |query| {
+                let emit = latest_emit.borrow().clone();
+                emit.emit(query);
+            });
+
+            QueryEmitter::new(*ms, emit)
         },
-        (props.query.clone(), props.oninput.clone()),
+        props.debounce_ms,
     );
 
-    let onkeypress = use_callback(
-        |event: KeyboardEvent, (query, onkeypress, oninput)| {
-            if event.key() == "Escape" {
-                if let Some(query) = query {
-                    query.set(String::new());
+    {
+        let emitter = emitter.clone();
+        let pending = pending.clone();
+
+        use_effect_with_deps(
+            move |(text, _)| {
+                if emitter.sync(text) {
+                    pending.set(false);
+                }
+            },
+            ((**text).clone(), props.debounce_ms),
+        );
+    }
+
+    let oninput = {
+        let emitter = emitter.clone();
+
+        use_callback(
+            move |event: String, (text, pending, open, active, _)| {
+                text.set(event.clone());
+                open.set(true);
+                active.set(None);
+
+                if emitter.input(event) {
+                    pending.set(true);
                 }
+            },
+            (
+                text.clone(),
+                pending.clone(),
+                open.clone(),
+                active.clone(),
+                props.debounce_ms,
+            ),
+        )
+    };
 
-                oninput.emit(String::new());
+    let record = use_callback(
+        |query: String, (history, key)| {
+            if let Some(key) = key {
+                let mut updated = (**history).clone();
+                updated.push(&query);
+                updated.save(key);
+
+                history.set(updated);
             }
+        },
+        (history.clone(), props.history_key.clone()),
+    );
+
+    let submit = {
+        let emitter = emitter.clone();
+
+        use_callback(
+            move |query: String, (record, open, onsubmit, _)| {
+                // a query that was already emitted isn't emitted again
+                emitter.flush();
+                record.emit(query.clone());
+                open.set(false);
+
+                onsubmit.emit(query);
+            },
+            (
+                record,
+                open.clone(),
+                props.onsubmit.clone(),
+                props.debounce_ms,
+            ),
+        )
+    };
+
+    let accept = {
+        let emitter = emitter.clone();
+
+        use_callback(
+            move |query: String, (text, submit, _)| {
+                text.set(query.clone());
+                emitter.emit(query.clone());
+                submit.emit(query);
+            },
+            (text.clone(), submit.clone(), props.debounce_ms),
+        )
+    };
 
-            onkeypress.emit(event);
+    let onkeydown = use_callback(
+        move |event: KeyboardEvent,
+              (text, options, open, active, accept, submit, _, keymap, node_ref, onkeydown)| {
+            let count = options.len();
+            let key = event.key();
+
+            match key.as_str() {
+                "ArrowDown" | "ArrowUp" if count > 0 => {
+                    event.prevent_default();
+
+                    let next = match (**open).then_some(**active).flatten() {
+                        None if key == "ArrowDown" => 0,
+                        None => count - 1,
+                        Some(i) if key == "ArrowDown" => (i + 1) % count,
+                        Some(i) => (i + count - 1) % count,
+                    };
+
+                    open.set(true);
+                    active.set(Some(next));
+                }
+                "Enter" => {
+                    let option = (**open)
+                        .then_some(**active)
+                        .flatten()
+                        .and_then(|i| options.get(i));
+
+                    match option {
+                        Some(option) => {
+                            event.prevent_default();
+                            accept.emit(option.text.clone());
+                        }
+                        None => submit.emit((**text).clone()),
+                    }
+                }
+                // closing the dropdown takes precedence over clearing
+                "Escape" if **open && count > 0 => {
+                    event.prevent_default();
+                    open.set(false);
+                }
+                _ => {}
+            }
+
+            onkeydown.emit(event.clone());
+
+            if event.default_prevented() {
+                return;
+            }
+
+            let Some(action) = keymap.event_action(&event) else {
+                return;
+            };
+
+            event.prevent_default();
+
+            match action {
+                InputAction::Clear => {
+                    text.set(String::new());
+                    emitter.emit(String::new());
+                }
+                InputAction::Blur => {
+                    if let Some(element) = node_ref.cast::<HtmlElement>() {
+                        element.blur().unwrap();
+                    }
+                }
+            }
         },
         (
-            props.query.clone(),
-            props.onkeypress.clone(),
-            props.oninput.clone(),
+            text.clone(),
+            options.clone(),
+            open.clone(),
+            active.clone(),
+            accept.clone(),
+            submit,
+            props.debounce_ms,
+            props.keymap.clone(),
+            props.node_ref.clone(),
+            props.onkeydown.clone(),
         ),
     );
 
+    let onfocus = use_callback(
+        |event: FocusEvent, (open, onfocus)| {
+            open.set(true);
+            onfocus.emit(event);
+        },
+        (open.clone(), props.onfocus.clone()),
+    );
+
+    let onblur = use_callback(
+        |event: FocusEvent, (open, active, onblur)| {
+            open.set(false);
+            active.set(None);
+            onblur.emit(event);
+        },
+        (open.clone(), active.clone(), props.onblur.clone()),
+    );
+
+    let class = classes!(
+        "garlic-search-query",
+        pending.then_some("garlic-pending"),
+        props.loading.then_some("garlic-loading"),
+        props.class.clone(),
+    );
+
+    // the invalid part of the query is highlighted under the input,
+    // until the query is changed
+    let invalid = invalid.as_ref().filter(|(query, _)| *query == **text);
+    let error = invalid.map(|(query, error)| {
+        html! {
+            <span class="garlic-input-error garlic-query-error" role="alert">
+                <HighlightedText text={ query.clone() } ranges={ vec![error.span()] }/>
+                { error.message() }
+            </span>
+        }
+    });
+
+    let input = html! {
+        <>
+            <TextInput
+                class={ class }
+                style={ props.style.clone() }
+                node_ref={ props.node_ref.clone() }
+                value={ (**text).clone() }
+                title={ props.title.clone() }
+                placeholder={ props.placeholder.clone() }
+                invalid={ invalid.is_some() }
+                oninput={ oninput }
+                keymap={ Keymap::new() }
+                onkeypress={ props.onkeypress.clone() }
+                onkeydown={ onkeydown }
+                onkeyup={ props.onkeyup.clone() }
+                onfocus={ onfocus }
+                onblur={ onblur }
+            />
+            { for error }
+        </>
+    };
+
+    if props.history_key.is_none() && props.suggestions.is_empty() {
+        return input;
+    }
+
+    let items = options.iter().enumerate().map(|(i, option)| {
+        let selected = Some(i) == *active;
+
+        // keeps the focus in the input, so clicking doesn't close the dropdown first
+        let onmousedown = Callback::from(|event: MouseEvent| event.prevent_default());
+        let onclick = {
+            let accept = accept.clone();
+            let text = option.text.clone();
+
+            Callback::from(move |_| accept.emit(text.clone()))
+        };
+
+        html! {
+            <li
+                key={ option.text.clone() }
+                class={ classes!(
+                    "garlic-search-suggestion",
+                    option.recent.then_some("garlic-recent"),
+                    selected.then_some("garlic-active"),
+                ) }
+                role="option"
+                aria-selected={ selected.to_string() }
+                onmousedown={ onmousedown }
+                onclick={ onclick }
+            >
+                <HighlightedText text={ option.text.clone() } ranges={ option.ranges.clone() }/>
+            </li>
+        }
+    });
+
     html! {
-        <TextInput
-            class={ classes!("garlic-search-query", props.class.clone()) }
-            style={ props.style.clone() }
-            node_ref={ props.node_ref.clone() }
-            value={ props.query.as_ref().map(|q| (**q).clone()) }
-            title={ props.title.clone() }
-            placeholder={ props.placeholder.clone() }
-            oninput={ oninput }
-            onkeypress={ onkeypress }
-            onkeydown={ props.onkeydown.clone() }
-            onkeyup={ props.onkeyup.clone() }
-            onfocus={ props.onfocus.clone() }
-            onblur={ props.onblur.clone() }
-        />
+        <div class="garlic-search">
+            { input }
+            if *open && !options.is_empty() {
+                <ul class="garlic-search-suggestions" role="listbox">
+                    { for items }
+                </ul>
+            }
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "input")]
+    use std::rc::Rc;
+
+    use super::*;
+    #[cfg(feature = "input")]
+    use crate::{set_clock, ManualClock};
+
+    fn term(term: &str) -> Query {
+        Query::Term(term.to_owned())
+    }
+
+    fn field(name: &str, filter: FieldFilter) -> Query {
+        Query::Field {
+            name: name.to_owned(),
+            filter,
+        }
+    }
+
+    fn error_span(query: &str) -> Range<usize> {
+        Query::parse(query).unwrap_err().span()
+    }
+
+    #[test]
+    fn parse_terms_and_fields() {
+        assert_eq!(Query::parse("").unwrap(), Query::And(vec![]));
+        assert_eq!(Query::parse("  open ").unwrap(), term("open"));
+
+        assert_eq!(
+            Query::parse(r#"status:open -tag:wip "exact phrase" priority:>2"#).unwrap(),
+            Query::And(vec![
+                field("status", FieldFilter::Text("open".to_owned())),
+                Query::Not(Box::new(field("tag", FieldFilter::Text("wip".to_owned())))),
+                Query::Phrase("exact phrase".to_owned()),
+                field("priority", FieldFilter::Compare(Comparison::Greater, 2.0)),
+            ])
+        );
+
+        assert_eq!(
+            Query::parse(r#"title:"a b" size:<=1.5 well-known"#).unwrap(),
+            Query::And(vec![
+                field("title", FieldFilter::Text("a b".to_owned())),
+                field("size", FieldFilter::Compare(Comparison::LessOrEqual, 1.5)),
+                term("well-known"),
+            ])
+        );
+    }
+
+    #[test]
+    fn or_binds_looser_than_and() {
+        assert_eq!(
+            Query::parse("a b OR c | -(d OR e)").unwrap(),
+            Query::Or(vec![
+                Query::And(vec![term("a"), term("b")]),
+                term("c"),
+                Query::Not(Box::new(Query::Or(vec![term("d"), term("e")]))),
+            ])
+        );
+
+        // only an uppercase `OR` is an operator
+        assert_eq!(
+            Query::parse("a or b").unwrap(),
+            Query::And(vec![term("a"), term("or"), term("b")])
+        );
+    }
+
+    #[test]
+    fn errors_have_spans() {
+        assert_eq!(error_span(r#"a "open"#), 2..7);
+        assert_eq!(error_span("a (b c"), 2..3);
+        assert_eq!(error_span("a b)"), 3..4);
+        assert_eq!(error_span("a ( ) b"), 2..5);
+        assert_eq!(error_span("status: open"), 0..7);
+        assert_eq!(error_span("priority:>high"), 10..14);
+        assert_eq!(error_span("priority:>"), 0..10);
+        assert_eq!(error_span("a - b"), 2..3);
+        assert_eq!(error_span("OR a"), 0..2);
+        assert_eq!(error_span("a OR"), 2..4);
+        assert_eq!(error_span("(a |)"), 3..4);
+
+        assert!(matches!(
+            Query::parse(r#"title:"open"#),
+            Err(ParseQueryError::UnterminatedPhrase { span }) if span == (6..11)
+        ));
+    }
+
+    #[cfg(feature = "input")]
+    fn query_emitter(ms: u32) -> (QueryEmitter, Rc<RefCell<Vec<String>>>) {
+        let emitted = Rc::new(RefCell::new(Vec::new()));
+
+        let emit = {
+            let emitted = emitted.clone();
+            Callback::from(move |query| emitted.borrow_mut().push(query))
+        };
+
+        (QueryEmitter::new(ms, emit), emitted)
+    }
+
+    #[cfg(feature = "input")]
+    #[test]
+    fn typed_queries_are_debounced() {
+        let clock = Rc::new(ManualClock::new());
+        set_clock(clock.clone());
+
+        let (emitter, emitted) = query_emitter(100);
+
+        assert!(emitter.input(String::from("o")));
+        clock.advance(60.0);
+        assert!(emitter.input(String::from("op")));
+        clock.advance(60.0);
+        assert!(emitted.borrow().is_empty());
+
+        clock.advance(40.0);
+        assert_eq!(*emitted.borrow(), ["op"]);
+
+        let (emitter, emitted) = query_emitter(0);
+        assert!(!emitter.input(String::from("o")));
+        assert_eq!(*emitted.borrow(), ["o"]);
+    }
+
+    #[cfg(feature = "input")]
+    #[test]
+    fn flush_only_emits_a_pending_query() {
+        let clock = Rc::new(ManualClock::new());
+        set_clock(clock.clone());
+
+        let (emitter, emitted) = query_emitter(100);
+
+        emitter.flush();
+        assert!(emitted.borrow().is_empty());
+
+        emitter.input(String::from("open"));
+        emitter.flush();
+        assert_eq!(*emitted.borrow(), ["open"]);
+
+        // submitting again, or the delay running out, doesn't emit it twice
+        emitter.flush();
+        clock.advance(100.0);
+        assert_eq!(*emitted.borrow(), ["open"]);
+    }
+
+    #[cfg(feature = "input")]
+    #[test]
+    fn parent_query_cancels_typed_query() {
+        let clock = Rc::new(ManualClock::new());
+        set_clock(clock.clone());
+
+        let (emitter, emitted) = query_emitter(100);
+
+        emitter.input(String::from("open"));
+        assert!(!emitter.sync("open"));
+
+        assert!(emitter.sync("closed"));
+        assert!(!emitter.sync("closed"));
+        clock.advance(100.0);
+        assert!(emitted.borrow().is_empty());
+
+        emitter.input(String::from("open"));
+        emitter.emit(String::new());
+        clock.advance(100.0);
+        assert_eq!(*emitted.borrow(), [""]);
     }
 }
@@ -1,10 +1,12 @@
 use std::{cell::RefCell, rc::Rc};
 
 use web_sys::{DomRect, HtmlElement};
-use yew::prelude::*;
+use yew::{prelude::*, virtual_dom::Key};
 
 use crate::{
-    use_animation_frame, AnimationFrame, DetectResize, Direction, DragArea, Draggable, Order, Style,
+    clock, use_animation_frame, use_stylesheet, AnimationFrame, AnimationFrameHandle, DetectResize,
+    Direction, DragArea, Draggable, Easing, Length, Order, Style, TransformFunction, Tween,
+    TweenConfig, UseOrderHistoryHandle,
 };
 
 #[derive(Properties, PartialEq)]
@@ -23,12 +25,63 @@ pub struct SortableListProps {
     pub contain: bool,
     #[prop_or_default]
     pub onorder: Callback<Order>,
+    /// Called when an item is dropped in a new place.
+    #[prop_or_default]
+    pub onmove: Callback<MoveEvent>,
+    /// Records moves so they can be undone, see [`use_order_history`](crate::use_order_history).
+    #[prop_or_default]
+    pub history: Option<UseOrderHistoryHandle>,
+}
+
+/// An item moved in a [`SortableList`].
+///
+/// Keys are the `key`s of the children of the list, `None` for children without one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MoveEvent {
+    pub key: Option<Key>,
+    /// The index of the item in the children of the list.
+    pub item: usize,
+    pub from_index: usize,
+    pub to_index: usize,
+    /// The key of the item now shown right before the moved item.
+    pub before_key: Option<Key>,
+    /// The key of the item now shown right after the moved item.
+    pub after_key: Option<Key>,
 }
 
+impl MoveEvent {
+    /// Creates the event for `item` after it's been moved in `order` from `from_index`.
+    fn new(order: &Order, item: usize, from_index: usize, keys: &[Option<Key>]) -> Self {
+        let to_index = order.index_of(item).unwrap();
+        let key_at = |index: Option<usize>| {
+            let item = *order.get(index?)?;
+            keys[item].clone()
+        };
+
+        Self {
+            key: keys[item].clone(),
+            item,
+            from_index,
+            to_index,
+            before_key: key_at(to_index.checked_sub(1)),
+            after_key: key_at(Some(to_index + 1)),
+        }
+    }
+}
+
+/// How a slide between two items is animated.
+const SLIDE_TWEEN: TweenConfig = TweenConfig {
+    duration: 200.0,
+    easing: Easing::EaseOut,
+};
+
 struct Slide {
     to: usize,
     from: usize,
-    time: f32,
+    /// Goes from `1.0` to `0.0` while sliding.
+    time: Tween<f32>,
+    last: f64,
+    frame: Option<AnimationFrameHandle>,
 }
 
 impl Slide {
@@ -36,33 +89,41 @@ impl Slide {
         Self {
             to: index,
             from: index,
-            time: 0.0,
+            time: Tween::with_config(0.0, SLIDE_TWEEN),
+            last: clock().now(),
+            frame: None,
         }
     }
 
     fn slide(&mut self, to: usize) {
         self.from = self.to;
         self.to = to;
-        self.time = 1.0;
+        self.time.set_value(1.0);
+        self.time.set_target(0.0);
+        self.last = clock().now();
     }
 
     fn get_offset(&self, index: usize) -> f32 {
+        let time = self.time.value();
+
         if index == self.to {
-            1.0 - self.time
+            1.0 - time
         } else if index == self.from {
-            self.time
+            time
         } else {
             0.0
         }
     }
 
     fn update(&mut self, frame: AnimationFrame) {
-        if self.time > 0.001 {
-            self.time *= 0.8;
+        let now = clock().now();
+        let delta = now - self.last;
+        self.last = now;
 
-            frame.request();
-        } else {
-            self.time = 0.0;
+        if !self.time.is_done() {
+            self.time.update(delta as f32);
+
+            self.frame = Some(frame.request());
         }
     }
 }
@@ -79,7 +140,7 @@ fn resize_child_state(
 
     node_refs.resize_with(len, Default::default);
     positions.resize_with(len, Default::default);
-    order.resize(len);
+    order.resize_items(len);
 
     true
 }
@@ -98,6 +159,10 @@ fn get_drag_size(drag: Option<usize>, node_refs: &[NodeRef], direction: Directio
     }
 }
 
+/// Gets the offset of the list within its offset parent.
+///
+/// `offset_left` and `offset_top` ignore transforms, so items translated by the list
+/// never feed their own transforms back into the layout.
 fn get_offset(node_ref: &NodeRef) -> (f32, f32) {
     if let Some(element) = node_ref.cast::<HtmlElement>() {
         (element.offset_left() as f32, element.offset_top() as f32)
@@ -119,6 +184,9 @@ fn expand_size(width: &mut f32, height: &mut f32, rect: &DomRect, direction: Dir
     }
 }
 
+/// Lays out the items, returning the size of the list.
+///
+/// Returns `None` until every item is mounted, like when rendering on the server.
 fn layout_items(
     order: &Order,
     node_refs: &[NodeRef],
@@ -127,17 +195,14 @@ fn layout_items(
     drag: Option<usize>,
     slide: &Option<Slide>,
     props: &SortableListProps,
-) -> (f32, f32) {
+) -> Option<(f32, f32)> {
     let (mut x, mut y) = get_offset(&props.node_ref);
     let drag_size = get_drag_size(drag, node_refs, props.direction);
 
     let mut width = 0.0f32;
     let mut height = 0.0f32;
     for (i, &o) in order.iter().enumerate() {
-        let Some(element) = node_refs[o].cast::<HtmlElement>() else {
-            continue;
-        };
-
+        let element = node_refs[o].cast::<HtmlElement>()?;
         let rect = element.get_bounding_client_rect();
 
         expand_size(&mut width, &mut height, &rect, direction);
@@ -150,22 +215,24 @@ fn layout_items(
             }
         }
 
-        positions[o] = (x, y);
-
+        // the dragged item is translated relative to where the drag started,
+        // so its position is kept until it's dropped
         if Some(i) == drag {
             continue;
         }
 
+        positions[o] = (x, y);
+
         match direction {
             Direction::Row => x += rect.width() as f32,
             Direction::Column => y += rect.height() as f32,
         }
     }
 
-    (width, height)
+    Some((width, height))
 }
 
-fn hovered_index(
+pub(crate) fn hovered_index(
     event: &crate::drag::DragEvent,
     node_refs: &[NodeRef],
     order: &Order,
@@ -206,7 +273,8 @@ fn hovered_index(
 
 fn render_items(
     node_refs: &[NodeRef],
-    positions: &[(f32, f32)],
+    // `None` until the items are measured
+    positions: Option<&[(f32, f32)]>,
     order: &Order,
     update: &UseForceUpdateHandle,
     drag: &UseStateHandle<Option<usize>>,
@@ -216,8 +284,7 @@ fn render_items(
     let mut items = Vec::with_capacity(props.children.len());
     for (o, child) in props.children.iter().enumerate() {
         let node_ref = node_refs[o].clone();
-        let (x, y) = positions[o];
-        let i = order.iter().position(|&i| i == o).unwrap();
+        let i = order.index_of(o).unwrap();
 
         let update = update.clone();
         let onresize = Callback::from(move |_| update.force_update());
@@ -230,14 +297,17 @@ fn render_items(
             slide.borrow_mut().replace(Slide::new(i));
         });
 
-        let style = Style::new()
-            .with("position", "absolute")
-            .with("user-select", "none")
-            .with("-webkit-user-select", "none")
-            .with("-moz-user-select", "none")
-            .with("-ms-user-select", "none")
-            .with("left", format!("{}px", x))
-            .with("top", format!("{}px", y));
+        let mut style = Style::new();
+
+        // unmeasured items are left in the flow, in source order
+        if let Some(&(x, y)) = positions.map(|positions| &positions[o]) {
+            style
+                .set("position", "absolute")
+                .set("will-change", "transform")
+                .left(Length::px(0.0))
+                .top(Length::px(0.0))
+                .transform(TransformFunction::translate_px(x, y));
+        }
 
         let child = html! {
             <Draggable
@@ -258,6 +328,8 @@ fn render_items(
 
 #[function_component]
 pub fn SortableList(props: &SortableListProps) -> Html {
+    use_stylesheet();
+
     let node_refs = use_mut_ref(Vec::<NodeRef>::new);
     let order = use_mut_ref(Order::new);
     let positions = use_mut_ref(Vec::<(f32, f32)>::new);
@@ -268,6 +340,30 @@ pub fn SortableList(props: &SortableListProps) -> Html {
     let update = use_force_update();
     let frame = use_animation_frame();
 
+    {
+        // items are measured once they're mounted
+        let update = update.clone();
+        use_effect_with_deps(move |_| update.force_update(), ());
+    }
+
+    use_effect_with_deps(
+        |(history, len)| {
+            if let Some(history) = history {
+                history.resize(*len);
+            }
+        },
+        (props.history.clone(), props.children.len()),
+    );
+
+    if let Some(history) = &props.history {
+        // the history owns the order, so undoing a move is shown here,
+        // it's resized after rendering so it isn't changed under the parent
+        let mut history_order = history.order();
+        history_order.resize_items(props.children.len());
+
+        *order.borrow_mut() = history_order;
+    }
+
     if resize_child_state(
         &mut node_refs.borrow_mut(),
         &mut order.borrow_mut(),
@@ -281,7 +377,7 @@ pub fn SortableList(props: &SortableListProps) -> Html {
         slide.update(frame);
     }
 
-    let (width, height) = layout_items(
+    let size = layout_items(
         &order.borrow(),
         &node_refs.borrow(),
         &mut positions.borrow_mut(),
@@ -291,9 +387,10 @@ pub fn SortableList(props: &SortableListProps) -> Html {
         props,
     );
 
+    let laid_out = positions.borrow();
     let items = render_items(
         &node_refs.borrow(),
-        &positions.borrow(),
+        size.map(|_| laid_out.as_slice()),
         &order.borrow(),
         &update,
         &drag,
@@ -333,22 +430,48 @@ pub fn SortableList(props: &SortableListProps) -> Html {
     };
 
     let ondrop = use_callback(
-        move |_, (order, drag, onorder)| {
+        move |_, (order, drag, keys, onorder, onmove, history)| {
             let slide = slide.borrow_mut().take().unwrap();
+            let from = drag.unwrap();
 
             let mut order = order.borrow_mut();
-            order.swap_move(drag.unwrap(), slide.to);
+            let before = order.clone();
+            let item = order[from];
+            order.swap_move(from, slide.to);
 
-            onorder.emit(order.clone());
+            if let Some(history) = history {
+                history.swap_move(from, slide.to);
+            }
+
+            if *order != before {
+                onorder.emit(order.clone());
+            }
+
+            if order[from] != item {
+                onmove.emit(MoveEvent::new(&order, item, from, keys));
+            }
 
             drag.set(None);
         },
-        (order.clone(), drag.clone(), props.onorder.clone()),
+        (
+            order.clone(),
+            drag.clone(),
+            props
+                .children
+                .iter()
+                .map(|child| child.key().cloned())
+                .collect::<Vec<_>>(),
+            props.onorder.clone(),
+            props.onmove.clone(),
+            props.history.clone(),
+        ),
     );
 
-    let mut style = Style::new()
-        .with("width", format!("{}px", width))
-        .with("height", format!("{}px", height));
+    let mut style = Style::new();
+
+    if let Some((width, height)) = size {
+        style.width(Length::px(width)).height(Length::px(height));
+    }
 
     style.parse(&props.style);
 
@@ -366,3 +489,54 @@ pub fn SortableList(props: &SortableListProps) -> Html {
         </DragArea>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::{set_clock, ManualClock};
+
+    #[test]
+    fn slide_tweens_by_elapsed_time() {
+        let clock = Rc::new(ManualClock::new());
+        set_clock(clock.clone());
+
+        let mut slide = Slide::new(0);
+        slide.slide(2);
+        assert_eq!(slide.get_offset(2), 0.0);
+        assert_eq!(slide.get_offset(0), 1.0);
+
+        clock.frame(SLIDE_TWEEN.duration as f64 / 2.0);
+        slide.update(AnimationFrame::new(|| {}));
+        assert!(slide.get_offset(2) > 0.5 && slide.get_offset(2) < 1.0);
+        assert!(slide.frame.is_some());
+
+        clock.frame(SLIDE_TWEEN.duration as f64);
+        slide.update(AnimationFrame::new(|| {}));
+        assert_eq!(slide.get_offset(2), 1.0);
+        assert_eq!(slide.get_offset(0), 0.0);
+    }
+
+    #[test]
+    fn move_event_has_neighbour_keys() {
+        let keys = [Some(Key::from("a")), None, Some(Key::from("c"))];
+
+        let mut order = Order::new();
+        order.resize(3);
+        order.swap_move(0, 2);
+
+        let event = MoveEvent::new(&order, 0, 0, &keys);
+        assert_eq!(event.key, Some(Key::from("a")));
+        assert_eq!(event.to_index, 1);
+        assert_eq!(event.before_key, None);
+        assert_eq!(event.after_key, Some(Key::from("c")));
+
+        order.swap_move(1, 3);
+
+        let event = MoveEvent::new(&order, 0, 1, &keys);
+        assert_eq!(event.to_index, 2);
+        assert_eq!(event.before_key, Some(Key::from("c")));
+        assert_eq!(event.after_key, None);
+    }
+}
@@ -0,0 +1,267 @@
+use web_sys::HtmlElement;
+use yew::{prelude::*, virtual_dom::Key};
+
+use crate::{
+    hovered_index, use_drag_area, use_draggable, use_stylesheet, Direction, DragAreaOptions,
+    DragContext, DragEvent, DragPosition, DraggableOptions, Order, Style, TransformFunction,
+};
+
+#[derive(Clone, Properties, PartialEq)]
+pub struct SortableRowProps {
+    #[prop_or_default]
+    pub class: Classes,
+    #[prop_or_default]
+    pub style: String,
+    #[prop_or_default]
+    pub children: Children,
+    /// Set by [`SortableTable`].
+    #[doc(hidden)]
+    #[prop_or_default]
+    pub node_ref: NodeRef,
+    /// Set by [`SortableTable`].
+    #[doc(hidden)]
+    #[prop_or_default]
+    pub ondrag: Callback<DragEvent>,
+}
+
+/// A `<tr>` that can be reordered inside a [`SortableTable`].
+#[function_component]
+pub fn SortableRow(props: &SortableRowProps) -> Html {
+    use_stylesheet();
+
+    let state = use_draggable(
+        props.node_ref.clone(),
+        DraggableOptions {
+            ondrag: props.ondrag.clone(),
+            ..Default::default()
+        },
+    );
+
+    let mut style = Style::new();
+
+    style.parse(&props.style);
+
+    if state.is_dragging {
+        // rows only move vertically, and stay in the table while dragged,
+        // which keeps their column widths
+        style.push_transform(TransformFunction::translate_px(
+            0.0,
+            state.transform.y as f32,
+        ));
+        style.set("will-change", "transform");
+        style.set("position", "relative");
+    }
+
+    html! {
+        <tr
+            class={ classes!(
+                "garlic-sortable-row",
+                state.is_dragging.then_some("garlic-dragging"),
+                props.class.clone(),
+            ) }
+            style={ style }
+            ref={ props.node_ref.clone() }
+        >
+            { for props.children.iter() }
+        </tr>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct SortableTableProps {
+    #[prop_or_default]
+    pub class: Classes,
+    #[prop_or_default]
+    pub style: String,
+    #[prop_or_default]
+    pub node_ref: NodeRef,
+    #[prop_or_default]
+    pub children: ChildrenWithProps<SortableRow>,
+    /// If true, dragging outside of the table body will be prevented.
+    #[prop_or_default]
+    pub contain: bool,
+    #[prop_or_default]
+    pub onorder: Callback<Order>,
+}
+
+/// Gets the vertical offset of the row at `index`, while the row at `drag` is hovering `to`.
+fn row_offset(index: usize, drag: usize, to: usize, drag_height: f32) -> f32 {
+    if drag < to && drag < index && index < to {
+        -drag_height
+    } else if to <= index && index < drag {
+        drag_height
+    } else {
+        0.0
+    }
+}
+
+fn get_drag_height(drag: Option<usize>, node_refs: &[NodeRef], order: &Order) -> f32 {
+    let Some(index) = drag else {
+        return 0.0;
+    };
+
+    match node_refs[order[index]].cast::<HtmlElement>() {
+        Some(element) => element.get_bounding_client_rect().height() as f32,
+        None => 0.0,
+    }
+}
+
+/// A `<tbody>` of [`SortableRow`]s that can be reordered by dragging.
+///
+/// Renders a `<tbody>`, so it must be placed directly inside a `<table>`.
+/// Rows are rendered in their current order.
+#[function_component]
+pub fn SortableTable(props: &SortableTableProps) -> Html {
+    let node_refs = use_mut_ref(Vec::<NodeRef>::new);
+    let order = use_mut_ref(Order::new);
+
+    let drag = use_state_eq(Option::<usize>::default);
+    let to = use_state_eq(Option::<usize>::default);
+
+    let len = props.children.len();
+    if node_refs.borrow().len() != len {
+        node_refs.borrow_mut().resize_with(len, Default::default);
+        order.borrow_mut().resize_items(len);
+    }
+
+    let onmove = use_callback(
+        |event: DragEvent, (node_refs, order, drag, to)| {
+            let index = hovered_index(
+                &event,
+                &node_refs.borrow(),
+                &order.borrow(),
+                **drag,
+                Direction::Column,
+            );
+
+            to.set(Some(index));
+        },
+        (node_refs.clone(), order.clone(), drag.clone(), to.clone()),
+    );
+
+    let ondrop = use_callback(
+        |_, (order, drag, to, onorder)| {
+            if let (Some(drag), Some(to)) = (**drag, **to) {
+                let mut order = order.borrow_mut();
+                let before = order.clone();
+                order.swap_move(drag, to);
+
+                // dropping a row where it was doesn't change the order
+                if *order != before {
+                    onorder.emit(order.clone());
+                }
+            }
+
+            drag.set(None);
+            to.set(None);
+        },
+        (
+            order.clone(),
+            drag.clone(),
+            to.clone(),
+            props.onorder.clone(),
+        ),
+    );
+
+    let (context, position) = use_drag_area(
+        props.node_ref.clone(),
+        DragAreaOptions {
+            contain: props.contain,
+            onmove,
+            ondrop,
+            ..Default::default()
+        },
+    );
+
+    let node_refs = node_refs.borrow();
+    let order = order.borrow();
+
+    let drag_height = get_drag_height(*drag, &node_refs, &order);
+
+    let children = props.children.iter().collect::<Vec<_>>();
+
+    let mut rows = Vec::with_capacity(children.len());
+    for (i, &o) in order.iter().enumerate() {
+        let mut row = children[o].props.as_ref().clone();
+
+        // rows without a key of their own are keyed by their item
+        let key = Html::from(children[o].clone())
+            .key()
+            .cloned()
+            .unwrap_or_else(|| Key::from(o));
+
+        let drag_handle = drag.clone();
+        let to_handle = to.clone();
+        row.ondrag = Callback::from(move |_| {
+            drag_handle.set(Some(i));
+            to_handle.set(Some(i));
+        });
+        row.node_ref = node_refs[o].clone();
+
+        let mut style = Style::new();
+
+        style.parse(&row.style);
+
+        if let (Some(drag), Some(to)) = (*drag, *to) {
+            if drag != i {
+                let offset = row_offset(i, drag, to, drag_height);
+
+                // the shift goes on top of the row's own transform
+                style.push_transform(TransformFunction::translate_px(0.0, offset));
+                style.set("will-change", "transform");
+                row.class.push("garlic-shifting");
+            }
+        }
+
+        row.style = style.to_string();
+
+        rows.push(html! {
+            <SortableRow { key } ..row />
+        });
+    }
+
+    html! {
+        <tbody
+            class={ classes!("garlic-sortable-table", props.class.clone()) }
+            style={ props.style.clone() }
+            ref={ props.node_ref.clone() }
+        >
+            <ContextProvider<DragContext> { context }>
+                <ContextProvider<DragPosition> context={ position }>
+                    { for rows }
+                </ContextProvider<DragPosition>>
+            </ContextProvider<DragContext>>
+        </tbody>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn row_offset_dragging_down() {
+        // the row at 1 hovers the gap before 4, so it lands between 3 and 4
+        let offsets: Vec<f32> = (0..6).map(|index| row_offset(index, 1, 4, 10.0)).collect();
+
+        assert_eq!(offsets, [0.0, 0.0, -10.0, -10.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn row_offset_dragging_up() {
+        // the row at 3 hovers the gap before 1, so it lands between 0 and 1
+        let offsets: Vec<f32> = (0..6).map(|index| row_offset(index, 3, 1, 10.0)).collect();
+
+        assert_eq!(offsets, [0.0, 10.0, 10.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn row_offset_no_op() {
+        // the gaps right before and after the dragged row don't move it
+        for to in [2, 3] {
+            let offsets: Vec<f32> = (0..6).map(|index| row_offset(index, 2, to, 10.0)).collect();
+
+            assert_eq!(offsets, [0.0; 6]);
+        }
+    }
+}
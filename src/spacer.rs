@@ -1,6 +1,6 @@
 use yew::prelude::*;
 
-use crate::{Direction, Style};
+use crate::{use_style, use_tween, Direction, Easing, Length, Style, TweenConfig};
 
 #[derive(Properties, PartialEq)]
 pub struct SpacerProps {
@@ -14,31 +14,33 @@ pub struct SpacerProps {
 
 #[function_component]
 pub fn Spacer(props: &SpacerProps) -> Html {
-    let size = use_mut_ref(|| props.size);
-
-    if f32::abs(*size.borrow() - props.size) > 0.1 {
-        if props.smooth {
-            let mut size = size.borrow_mut();
-            let diff = props.size - *size;
-            *size += diff / 2.0;
-        } else {
-            *size.borrow_mut() = props.size;
-        }
-    } else if *size.borrow() != props.size {
-        *size.borrow_mut() = props.size;
-    }
+    let tween = TweenConfig {
+        duration: if props.smooth { 150.0 } else { 0.0 },
+        easing: Easing::EaseOut,
+    };
+
+    let node_ref = use_node_ref();
+    let size = use_tween(props.size, tween);
 
     let mut style = Style::new();
 
-    let size = size.borrow();
     if props.direction.is_vertical() {
-        style.set("height", &format!("{}px", size));
+        style.height(Length::px(size));
     } else {
-        style.set("width", &format!("{}px", size));
+        style.width(Length::px(size));
     }
 
+    // the first style is rendered inline, so the spacer has its size before it's mounted,
+    // after that the size changes every frame while smooth, so only it is updated
+    let initial = {
+        let style = style.clone();
+        use_memo(move |_| style.to_string(), ())
+    };
+
+    use_style(node_ref.clone(), style);
+
     html! {
-        <div class="garlic-spacer" {style}>
+        <div class="garlic-spacer" style={ (*initial).clone() } ref={ node_ref }>
         </div>
     }
 }
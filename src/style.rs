@@ -1,13 +1,25 @@
-use std::ops::{Add, AddAssign};
+use std::{
+    fmt::{self, Display},
+    ops::{Add, AddAssign},
+    str::FromStr,
+};
 
+#[cfg(feature = "web")]
 use implicit_clone::unsync::IString;
 use smallvec::SmallVec;
+#[cfg(feature = "web")]
 use yew::html::IntoPropValue;
 
-#[derive(Clone, Debug, Default)]
+use crate::{
+    style_parser::parse_declarations, Color, Length, ParseStyleError, Transform, TransformFunction,
+};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct StyleTag {
     pub name: String,
     pub value: String,
+    /// True if the tag is marked `!important`.
+    pub important: bool,
 }
 
 impl StyleTag {
@@ -16,11 +28,18 @@ impl StyleTag {
         Self {
             name: name.into(),
             value: value.into(),
+            important: false,
         }
     }
+
+    #[inline]
+    pub fn important(mut self) -> Self {
+        self.important = true;
+        self
+    }
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Style {
     pub tags: SmallVec<[StyleTag; 8]>,
 }
@@ -30,63 +49,237 @@ impl Style {
         Self::default()
     }
 
+    /// Parses a CSS declaration list and cascades every tag in it, see [`Style::cascade_tag`].
+    ///
+    /// Malformed declarations are skipped, like a browser would,
+    /// see [`Style::try_parse`] to report them.
     #[inline]
     pub fn parse(&mut self, style: impl AsRef<str>) {
-        let mut style = style.as_ref().split(';');
+        for tag in parse_declarations(style.as_ref()).into_iter().flatten() {
+            self.cascade_tag(tag);
+        }
+    }
 
-        while let Some(tag) = style.next() {
-            let mut tag = tag.split(':');
-            let name = tag.next().unwrap_or_default().trim();
-            let value = tag.next().unwrap_or_default().trim();
+    /// Parses a CSS declaration list and cascades every tag in it, see [`Style::cascade_tag`].
+    ///
+    /// If any declaration is malformed, nothing is set and the first error is returned.
+    #[inline]
+    pub fn try_parse(&mut self, style: impl AsRef<str>) -> Result<(), ParseStyleError> {
+        let tags = parse_declarations(style.as_ref())
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
 
-            if !name.is_empty() && !value.is_empty() {
-                self.set(name, value);
-            }
+        for tag in tags {
+            self.cascade_tag(tag);
         }
+
+        Ok(())
     }
 
+    /// Sets the tag `name` to `value`, replacing it even if it's marked `!important`.
     #[inline]
     pub fn set(
         &mut self,
         name: impl Into<String> + AsRef<str>,
         value: impl Into<String>,
     ) -> &mut Self {
+        self.set_tag(StyleTag::new(name, value))
+    }
+
+    /// Sets the tag `name` to `value` and marks it `!important`.
+    #[inline]
+    pub fn set_important(
+        &mut self,
+        name: impl Into<String> + AsRef<str>,
+        value: impl Into<String>,
+    ) -> &mut Self {
+        self.set_tag(StyleTag::new(name, value).important())
+    }
+
+    #[inline]
+    pub fn set_tag(&mut self, new_tag: StyleTag) -> &mut Self {
         for tag in &mut self.tags {
-            if tag.name == name.as_ref() {
-                tag.value = value.into();
+            if tag.name == new_tag.name {
+                *tag = new_tag;
                 return self;
             }
         }
 
-        self.tags.push(StyleTag {
-            name: name.into(),
-            value: value.into(),
-        });
+        self.tags.push(new_tag);
 
         self
     }
 
+    /// Sets a tag like a later declaration in CSS would, returning whether it was set.
+    ///
+    /// A tag marked `!important` is only overridden by another important tag.
+    #[inline]
+    pub fn cascade_tag(&mut self, new_tag: StyleTag) -> bool {
+        let overridden = self
+            .tags
+            .iter()
+            .any(|tag| tag.name == new_tag.name && tag.important && !new_tag.important);
+
+        if !overridden {
+            self.set_tag(new_tag);
+        }
+
+        !overridden
+    }
+
+    #[inline]
+    pub fn get(&self, name: impl AsRef<str>) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|tag| tag.name == name.as_ref())
+            .map(|tag| tag.value.as_str())
+    }
+
+    #[inline]
+    pub fn get_tag(&self, name: impl AsRef<str>) -> Option<&StyleTag> {
+        self.tags.iter().find(|tag| tag.name == name.as_ref())
+    }
+
+    #[inline]
+    pub fn left(&mut self, left: Length) -> &mut Self {
+        self.set("left", left.to_string())
+    }
+
+    #[inline]
+    pub fn top(&mut self, top: Length) -> &mut Self {
+        self.set("top", top.to_string())
+    }
+
+    #[inline]
+    pub fn right(&mut self, right: Length) -> &mut Self {
+        self.set("right", right.to_string())
+    }
+
+    #[inline]
+    pub fn bottom(&mut self, bottom: Length) -> &mut Self {
+        self.set("bottom", bottom.to_string())
+    }
+
+    #[inline]
+    pub fn width(&mut self, width: Length) -> &mut Self {
+        self.set("width", width.to_string())
+    }
+
+    #[inline]
+    pub fn height(&mut self, height: Length) -> &mut Self {
+        self.set("height", height.to_string())
+    }
+
+    #[inline]
+    pub fn z_index(&mut self, z_index: i32) -> &mut Self {
+        self.set("z-index", z_index.to_string())
+    }
+
+    #[inline]
+    pub fn opacity(&mut self, opacity: f32) -> &mut Self {
+        self.set("opacity", opacity.to_string())
+    }
+
+    #[inline]
+    pub fn color(&mut self, color: Color) -> &mut Self {
+        self.set("color", color.to_string())
+    }
+
+    #[inline]
+    pub fn background_color(&mut self, color: Color) -> &mut Self {
+        self.set("background-color", color.to_string())
+    }
+
+    #[inline]
+    pub fn transform(&mut self, transform: impl Into<Transform>) -> &mut Self {
+        self.set("transform", transform.into().to_string())
+    }
+
+    /// Appends `function` to the current transform, applying it after the existing ones.
+    ///
+    /// The transform stays `!important` if it was.
+    #[inline]
+    pub fn push_transform(&mut self, function: TransformFunction) -> &mut Self {
+        let current = self.tags.iter().find(|tag| tag.name == "transform");
+        let important = current.is_some_and(|tag| tag.important);
+
+        let transform = match current {
+            Some(tag) if tag.value != "none" => format!("{} {}", tag.value, function),
+            _ => function.to_string(),
+        };
+
+        let tag = StyleTag::new("transform", transform);
+
+        self.set_tag(if important { tag.important() } else { tag })
+    }
+
     #[inline]
     pub fn with(mut self, name: impl Into<String> + AsRef<str>, value: impl Into<String>) -> Self {
         self.set(name, value);
         self
     }
 
+    /// Gets the changes needed to go from `previous` to this style.
+    pub fn diff<'a>(&'a self, previous: &'a Style) -> StyleDiff<'a> {
+        let set = self
+            .tags
+            .iter()
+            .filter(|tag| previous.get_tag(&tag.name) != Some(*tag))
+            .collect();
+
+        let remove = previous
+            .tags
+            .iter()
+            .filter(|tag| self.get_tag(&tag.name).is_none())
+            .map(|tag| tag.name.as_str())
+            .collect();
+
+        StyleDiff { set, remove }
+    }
+}
+
+/// The changes between two [`Style`]s, see [`Style::diff`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StyleDiff<'a> {
+    /// Tags that were added or changed.
+    pub set: Vec<&'a StyleTag>,
+    /// Names of tags that were removed.
+    pub remove: Vec<&'a str>,
+}
+
+impl StyleDiff<'_> {
     #[inline]
-    pub fn to_string(&self) -> String {
-        let mut style = String::new();
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty() && self.remove.is_empty()
+    }
+}
 
+impl Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for tag in &self.tags {
-            style.push_str(&tag.name);
-            style.push(':');
-            style.push_str(&tag.value);
-            style.push(';');
+            if tag.important {
+                write!(f, "{}:{} !important;", tag.name, tag.value)?;
+            } else {
+                write!(f, "{}:{};", tag.name, tag.value)?;
+            }
         }
 
-        style
+        Ok(())
     }
 }
 
+impl FromStr for Style {
+    type Err = ParseStyleError;
+
+    #[inline]
+    fn from_str(style: &str) -> Result<Self, Self::Err> {
+        let mut parsed = Self::new();
+        parsed.try_parse(style)?;
+        Ok(parsed)
+    }
+}
+
+#[cfg(feature = "web")]
 impl IntoPropValue<Option<IString>> for Style {
     fn into_prop_value(self) -> Option<IString> {
         if self.tags.is_empty() {
@@ -97,6 +290,7 @@ impl IntoPropValue<Option<IString>> for Style {
     }
 }
 
+#[cfg(feature = "web")]
 impl IntoPropValue<String> for Style {
     fn into_prop_value(self) -> String {
         self.to_string()
@@ -115,7 +309,138 @@ impl Add<Style> for Style {
 impl AddAssign<Style> for Style {
     fn add_assign(&mut self, other: Style) {
         for tag in other.tags {
-            self.set(tag.name, tag.value);
+            self.set_tag(tag);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(style: &str) -> Style {
+        let mut parsed = Style::new();
+        parsed.parse(style);
+        parsed
+    }
+
+    #[test]
+    fn parse_simple_declarations() {
+        let style = parse(" width : 10px ; height:20px;;");
+
+        assert_eq!(style.get("width"), Some("10px"));
+        assert_eq!(style.get("height"), Some("20px"));
+        assert_eq!(style.to_string(), "width:10px;height:20px;");
+    }
+
+    #[test]
+    fn parse_keeps_colons_and_semicolons_in_values() {
+        let style = parse("background:url(http://x/a;b.png);content:\"a;b:c\";font-family:'x;y'");
+
+        assert_eq!(style.get("background"), Some("url(http://x/a;b.png)"));
+        assert_eq!(style.get("content"), Some("\"a;b:c\""));
+        assert_eq!(style.get("font-family"), Some("'x;y'"));
+    }
+
+    #[test]
+    fn parse_escapes_and_comments() {
+        let style = parse("/* a; b */ content: \"a\\\";b\"; color: red /* c: d; */;");
+
+        assert_eq!(style.get("content"), Some("\"a\\\";b\""));
+        assert_eq!(style.get("color"), Some("red"));
+    }
+
+    #[test]
+    fn parse_important() {
+        let mut style = parse("cursor: move ! IMPORTANT; color: red !important");
+
+        assert!(style.tags.iter().all(|tag| tag.important));
+        assert_eq!(style.get("cursor"), Some("move"));
+
+        assert!(!style.cascade_tag(StyleTag::new("cursor", "grab")));
+        assert_eq!(style.get("cursor"), Some("move"));
+
+        style.parse("color: blue");
+        assert_eq!(style.get("color"), Some("red"));
+
+        assert!(style.cascade_tag(StyleTag::new("cursor", "grab").important()));
+        assert_eq!(
+            style.to_string(),
+            "cursor:grab !important;color:red !important;"
+        );
+
+        style.set("cursor", "move");
+        assert_eq!(style.to_string(), "cursor:move;color:red !important;");
+
+        let mut style = parse("transform: scale(2) !important");
+        style.push_transform(TransformFunction::Rotate(90.0));
+        assert_eq!(
+            style.to_string(),
+            "transform:scale(2) rotate(90deg) !important;"
+        );
+    }
+
+    #[test]
+    fn parse_skips_malformed_declarations() {
+        let style = parse("width; :10px; height:; color: red");
+
+        assert_eq!(style.to_string(), "color:red;");
+    }
+
+    #[test]
+    fn try_parse_reports_errors() {
+        let cases = [
+            (
+                "color: red; width",
+                ParseStyleError::MissingColon { position: 11 },
+            ),
+            (":red", ParseStyleError::EmptyName { position: 0 }),
+            ("color:", ParseStyleError::EmptyValue { position: 0 }),
+            (
+                "content: \"a",
+                ParseStyleError::UnterminatedString { position: 9 },
+            ),
+            (
+                "color: red /* x",
+                ParseStyleError::UnterminatedComment { position: 11 },
+            ),
+            (
+                "background: url(x",
+                ParseStyleError::UnbalancedParenthesis { position: 15 },
+            ),
+            (
+                "width: 10px)",
+                ParseStyleError::UnbalancedParenthesis { position: 11 },
+            ),
+        ];
+
+        for (input, error) in cases {
+            assert_eq!(input.parse::<Style>().unwrap_err(), error, "{}", input);
+        }
+
+        let mut style = Style::new();
+        assert!(style.try_parse("color: red; width").is_err());
+        assert!(style.tags.is_empty());
+    }
+
+    #[test]
+    fn diff_only_changed_tags() {
+        let previous = parse("left:0px;top:0px;opacity:1;cursor:grab");
+        let style = parse("left:0px;top:10px;cursor:grab !important;color:red");
+
+        let diff = style.diff(&previous);
+        let set = diff.set.iter().copied().cloned().collect::<Vec<_>>();
+
+        assert_eq!(
+            set,
+            [
+                StyleTag::new("top", "10px"),
+                StyleTag::new("cursor", "grab").important(),
+                StyleTag::new("color", "red"),
+            ]
+        );
+        assert_eq!(diff.remove, ["opacity"]);
+
+        assert!(style.diff(&style.clone()).is_empty());
+    }
+}
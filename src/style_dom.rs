@@ -0,0 +1,60 @@
+use web_sys::{CssStyleDeclaration, HtmlElement};
+use yew::prelude::*;
+
+use crate::Style;
+
+impl Style {
+    /// Applies the changes from `previous` to this style directly to `declaration`.
+    ///
+    /// Only changed properties are touched, unlike replacing the whole `style` attribute.
+    pub fn apply_diff(&self, previous: &Style, declaration: &CssStyleDeclaration) {
+        let diff = self.diff(previous);
+
+        for name in diff.remove {
+            let _ = declaration.remove_property(name);
+        }
+
+        for tag in diff.set {
+            let priority = if tag.important { "important" } else { "" };
+            let _ = declaration.set_property_with_priority(&tag.name, &tag.value, priority);
+        }
+    }
+
+    /// Applies the changes from `previous` to the element referenced by `node_ref`.
+    ///
+    /// Does nothing if `node_ref` isn't bound to an [`HtmlElement`].
+    #[inline]
+    pub fn apply_to(&self, previous: &Style, node_ref: &NodeRef) {
+        if let Some(element) = node_ref.cast::<HtmlElement>() {
+            self.apply_diff(previous, &element.style());
+        }
+    }
+}
+
+/// Applies `style` to the element referenced by `node_ref` after every render.
+///
+/// Only properties changed since the last render are set. The element may also get a
+/// `style` attribute from `html!`, so it's styled before it's mounted or on the server,
+/// which is read back as the starting point when the element is mounted. That attribute
+/// must not change afterwards, or it would overwrite the applied properties.
+#[hook]
+pub fn use_style(node_ref: NodeRef, style: Style) {
+    let applied = use_mut_ref(|| (None::<HtmlElement>, Style::new()));
+
+    use_effect(move || {
+        if let Some(element) = node_ref.cast::<HtmlElement>() {
+            let mut applied = applied.borrow_mut();
+
+            // a new element only has the properties of its `style` attribute
+            if applied.0.as_ref() != Some(&element) {
+                let mut initial = Style::new();
+                initial.parse(element.get_attribute("style").unwrap_or_default());
+
+                applied.1 = initial;
+            }
+
+            style.apply_diff(&applied.1, &element.style());
+            *applied = (Some(element), style);
+        }
+    });
+}
@@ -0,0 +1,262 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    iter::Peekable,
+    str::CharIndices,
+};
+
+use crate::StyleTag;
+
+/// An error produced when parsing a malformed CSS declaration list.
+///
+/// Positions are byte offsets into the parsed string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseStyleError {
+    UnterminatedString {
+        position: usize,
+    },
+    UnterminatedComment {
+        position: usize,
+    },
+    UnbalancedParenthesis {
+        position: usize,
+    },
+    /// A declaration without a `:`.
+    MissingColon {
+        position: usize,
+    },
+    EmptyName {
+        position: usize,
+    },
+    EmptyValue {
+        position: usize,
+    },
+}
+
+impl ParseStyleError {
+    #[inline]
+    pub const fn position(&self) -> usize {
+        match *self {
+            ParseStyleError::UnterminatedString { position }
+            | ParseStyleError::UnterminatedComment { position }
+            | ParseStyleError::UnbalancedParenthesis { position }
+            | ParseStyleError::MissingColon { position }
+            | ParseStyleError::EmptyName { position }
+            | ParseStyleError::EmptyValue { position } => position,
+        }
+    }
+}
+
+impl Display for ParseStyleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ParseStyleError::UnterminatedString { .. } => "unterminated string",
+            ParseStyleError::UnterminatedComment { .. } => "unterminated comment",
+            ParseStyleError::UnbalancedParenthesis { .. } => "unbalanced parenthesis",
+            ParseStyleError::MissingColon { .. } => "declaration is missing a `:`",
+            ParseStyleError::EmptyName { .. } => "declaration has an empty name",
+            ParseStyleError::EmptyValue { .. } => "declaration has an empty value",
+        };
+
+        write!(f, "{} at {}", message, self.position())
+    }
+}
+
+impl Error for ParseStyleError {}
+
+/// A declaration being scanned.
+#[derive(Default)]
+struct Declaration {
+    start: usize,
+    text: String,
+    /// The offset of the first top level `:` in `text`.
+    colon: Option<usize>,
+    /// The offset of the last top level `!` in `text`.
+    bang: Option<usize>,
+    error: Option<ParseStyleError>,
+}
+
+impl Declaration {
+    fn new(start: usize) -> Self {
+        Self {
+            start,
+            ..Default::default()
+        }
+    }
+
+    fn fail(&mut self, error: ParseStyleError) {
+        self.error.get_or_insert(error);
+    }
+
+    fn finish(self) -> Option<Result<StyleTag, ParseStyleError>> {
+        if let Some(error) = self.error {
+            return Some(Err(error));
+        }
+
+        if self.text.trim().is_empty() {
+            return None;
+        }
+
+        let Some(colon) = self.colon else {
+            return Some(Err(ParseStyleError::MissingColon {
+                position: self.start,
+            }));
+        };
+
+        let name = self.text[..colon].trim();
+        if name.is_empty() {
+            return Some(Err(ParseStyleError::EmptyName {
+                position: self.start,
+            }));
+        }
+
+        let mut value = &self.text[colon + 1..];
+        let mut important = false;
+
+        if let Some(bang) = self.bang.filter(|&bang| bang > colon) {
+            if self.text[bang + 1..]
+                .trim()
+                .eq_ignore_ascii_case("important")
+            {
+                value = &self.text[colon + 1..bang];
+                important = true;
+            }
+        }
+
+        let value = value.trim();
+        if value.is_empty() {
+            return Some(Err(ParseStyleError::EmptyValue {
+                position: self.start,
+            }));
+        }
+
+        let mut tag = StyleTag::new(name, value);
+        tag.important = important;
+
+        Some(Ok(tag))
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<CharIndices<'a>>,
+    declarations: Vec<Result<StyleTag, ParseStyleError>>,
+    current: Declaration,
+    /// Open parentheses and brackets, with their positions.
+    depth: Vec<(char, usize)>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(style: &'a str) -> Self {
+        Self {
+            chars: style.char_indices().peekable(),
+            declarations: Vec::new(),
+            current: Declaration::new(0),
+            depth: Vec::new(),
+        }
+    }
+
+    fn finish_declaration(&mut self, next: usize) {
+        let declaration = std::mem::replace(&mut self.current, Declaration::new(next));
+
+        if let Some(declaration) = declaration.finish() {
+            self.declarations.push(declaration);
+        }
+    }
+
+    /// Copies an escaped character verbatim.
+    fn escape(&mut self) {
+        self.current.text.push('\\');
+
+        if let Some((_, c)) = self.chars.next() {
+            self.current.text.push(c);
+        }
+    }
+
+    fn string(&mut self, quote: char, position: usize) {
+        self.current.text.push(quote);
+
+        while let Some((_, c)) = self.chars.next() {
+            match c {
+                '\\' => self.escape(),
+                c if c == quote => {
+                    self.current.text.push(c);
+                    return;
+                }
+                c => self.current.text.push(c),
+            }
+        }
+
+        self.current
+            .fail(ParseStyleError::UnterminatedString { position });
+    }
+
+    fn comment(&mut self, position: usize) {
+        // skip the `*`
+        self.chars.next();
+
+        while let Some((_, c)) = self.chars.next() {
+            if c == '*' && matches!(self.chars.peek(), Some((_, '/'))) {
+                self.chars.next();
+
+                // comments separate tokens like whitespace
+                self.current.text.push(' ');
+                return;
+            }
+        }
+
+        self.current
+            .fail(ParseStyleError::UnterminatedComment { position });
+    }
+
+    fn parse(mut self) -> Vec<Result<StyleTag, ParseStyleError>> {
+        while let Some((position, c)) = self.chars.next() {
+            match c {
+                '/' if matches!(self.chars.peek(), Some((_, '*'))) => self.comment(position),
+                '\\' => self.escape(),
+                '"' | '\'' => self.string(c, position),
+                '(' | '[' => {
+                    self.depth.push((c, position));
+                    self.current.text.push(c);
+                }
+                ')' | ']' => {
+                    let open = if c == ')' { '(' } else { '[' };
+
+                    match self.depth.pop() {
+                        Some((o, _)) if o == open => {}
+                        _ => self
+                            .current
+                            .fail(ParseStyleError::UnbalancedParenthesis { position }),
+                    }
+
+                    self.current.text.push(c);
+                }
+                ':' if self.depth.is_empty() && self.current.colon.is_none() => {
+                    self.current.colon = Some(self.current.text.len());
+                    self.current.text.push(c);
+                }
+                '!' if self.depth.is_empty() => {
+                    self.current.bang = Some(self.current.text.len());
+                    self.current.text.push(c);
+                }
+                ';' if self.depth.is_empty() => self.finish_declaration(position + 1),
+                c => self.current.text.push(c),
+            }
+        }
+
+        if let Some(&(_, position)) = self.depth.first() {
+            self.current
+                .fail(ParseStyleError::UnbalancedParenthesis { position });
+        }
+
+        self.finish_declaration(0);
+        self.declarations
+    }
+}
+
+/// Parses a CSS declaration list, like the contents of a `style` attribute.
+///
+/// Strings, parentheses, comments and escapes are respected. Malformed declarations
+/// are reported in place, the rest are still parsed.
+pub(crate) fn parse_declarations(style: &str) -> Vec<Result<StyleTag, ParseStyleError>> {
+    Parser::new(style).parse()
+}
@@ -0,0 +1,167 @@
+use std::{borrow::Cow, cell::RefCell};
+
+use yew::prelude::*;
+
+/// The default stylesheet, with the base styles of every `garlic-*` class.
+///
+/// Selectors are wrapped in `:where`, so any app style overrides them without
+/// `!important`. Theming is done with custom properties, set on any ancestor:
+///
+/// - `--garlic-drag-cursor`, the cursor over draggable elements.
+/// - `--garlic-dragging-cursor`, the cursor while dragging.
+/// - `--garlic-drag-z-index`, the z-index of dragged elements, which are made
+///   `position: relative` unless the app positions them.
+/// - `--garlic-placeholder-color`, the placeholder color of inputs.
+/// - `--garlic-invalid-color`, the color of invalid inputs and their errors.
+/// - `--garlic-dropdown-z-index`, the z-index of the search suggestions.
+/// - `--garlic-transition-duration` and `--garlic-transition-easing`, used when items
+///   make room for a dragged item.
+pub const STYLESHEET: &str = "\
+:where(.garlic-draggable, .garlic-sortable-row) {
+    cursor: var(--garlic-drag-cursor, grab);
+    touch-action: none;
+}
+:where(.garlic-draggable.garlic-dragging, .garlic-sortable-row.garlic-dragging) {
+    cursor: var(--garlic-dragging-cursor, grabbing);
+    position: relative;
+    z-index: var(--garlic-drag-z-index, 1000);
+}
+:where(.garlic-list-item, .garlic-sortable-row) {
+    user-select: none;
+    -webkit-user-select: none;
+    -moz-user-select: none;
+    -ms-user-select: none;
+}
+:where(.garlic-sortable-row.garlic-shifting) {
+    transition: transform var(--garlic-transition-duration, 150ms)
+        var(--garlic-transition-easing, ease);
+}
+:where(.garlic-text-input, .garlic-search-query)::placeholder {
+    color: var(--garlic-placeholder-color, #888);
+}
+:where(.garlic-text-input.garlic-invalid) {
+    border-color: var(--garlic-invalid-color, #d33);
+    outline-color: var(--garlic-invalid-color, #d33);
+}
+:where(.garlic-input-error) {
+    display: block;
+    color: var(--garlic-invalid-color, #d33);
+    font-size: smaller;
+}
+:where(.garlic-query-error .garlic-highlight) {
+    background: none;
+    color: inherit;
+    text-decoration: underline wavy var(--garlic-invalid-color, #d33);
+}
+:where(.garlic-search-query.garlic-pending, .garlic-search-query.garlic-loading) {
+    cursor: progress;
+}
+:where(.garlic-search) {
+    position: relative;
+    display: inline-block;
+}
+:where(.garlic-search-suggestions) {
+    position: absolute;
+    top: 100%;
+    left: 0;
+    z-index: var(--garlic-dropdown-z-index, 100);
+    box-sizing: border-box;
+    min-width: 100%;
+    margin: 0;
+    padding: 0;
+    list-style: none;
+    background: Canvas;
+    color: CanvasText;
+    border: 1px solid #888;
+}
+:where(.garlic-search-suggestion) {
+    padding: 0.25em 0.5em;
+    cursor: pointer;
+}
+:where(.garlic-search-suggestion.garlic-active) {
+    background: Highlight;
+    color: HighlightText;
+}
+";
+
+/// The id of the injected `<style>` element.
+pub const STYLESHEET_ID: &str = "garlic-stylesheet";
+
+thread_local! {
+    static SOURCE: RefCell<Option<Cow<'static, str>>> = const { RefCell::new(Some(Cow::Borrowed(STYLESHEET))) };
+}
+
+fn stylesheet_element() -> Option<web_sys::Element> {
+    web_sys::window()?
+        .document()?
+        .get_element_by_id(STYLESHEET_ID)
+}
+
+/// Replaces the stylesheet injected by garlic components.
+///
+/// If the stylesheet has already been injected, it's updated in place.
+#[inline]
+pub fn set_stylesheet(css: impl Into<Cow<'static, str>>) {
+    let css = css.into();
+
+    if let Some(element) = stylesheet_element() {
+        element.set_text_content(Some(&css));
+    }
+
+    SOURCE.with(|source| *source.borrow_mut() = Some(css));
+}
+
+/// Stops garlic components from injecting a stylesheet, removing it if already injected.
+///
+/// Components then only keep the inline styles they need to work.
+#[inline]
+pub fn disable_stylesheet() {
+    if let Some(element) = stylesheet_element() {
+        element.remove();
+    }
+
+    SOURCE.with(|source| *source.borrow_mut() = None);
+}
+
+/// Injects the stylesheet into the document head, unless it's already there or disabled.
+pub fn inject_stylesheet() {
+    SOURCE.with(|source| {
+        let source = source.borrow();
+
+        let Some(css) = source.as_ref() else {
+            return;
+        };
+
+        if stylesheet_element().is_some() {
+            return;
+        }
+
+        let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+            return;
+        };
+
+        let Some(head) = document.head() else {
+            return;
+        };
+
+        let element = document
+            .create_element("style")
+            .expect("garlic failed to create stylesheet");
+        element.set_id(STYLESHEET_ID);
+        element.set_text_content(Some(css));
+
+        head.append_child(&element)
+            .expect("garlic failed to inject stylesheet");
+    });
+}
+
+/// Injects the garlic stylesheet when the component is mounted.
+#[hook]
+pub fn use_stylesheet() {
+    use_effect_with_deps(
+        |_| {
+            inject_stylesheet();
+        },
+        (),
+    );
+}
@@ -1,7 +1,9 @@
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
-#[derive(Properties, PartialEq)]
+use crate::{use_stylesheet, InputAction, Keymap};
+
+#[derive(Properties, PartialEq, Clone)]
 pub struct TextInputProps {
     #[prop_or_default]
     pub class: Classes,
@@ -14,6 +16,21 @@ pub struct TextInputProps {
     pub placeholder: Option<String>,
     #[prop_or_default]
     pub autofocus: bool,
+    /// The `type` of the input, like `"password"` or `"email"`.
+    #[prop_or_else(|| String::from("text"))]
+    pub input_type: String,
+    /// The `inputmode` attribute, the virtual keyboard shown on mobile, like `"decimal"`.
+    pub inputmode: Option<String>,
+    /// The `min`, `max` and `step` attributes, for number inputs.
+    pub min: Option<String>,
+    pub max: Option<String>,
+    pub step: Option<String>,
+    /// Adds the `garlic-invalid` class and `aria-invalid`.
+    #[prop_or_default]
+    pub invalid: bool,
+    /// The shortcuts handled on `keydown`, unless `onkeydown` prevents the default.
+    #[prop_or_default]
+    pub keymap: Keymap,
     #[prop_or_default]
     pub oninput: Callback<String>,
     #[prop_or_default]
@@ -28,8 +45,17 @@ pub struct TextInputProps {
     pub onblur: Callback<FocusEvent>,
 }
 
+impl Default for TextInputProps {
+    #[inline]
+    fn default() -> Self {
+        yew::props!(TextInputProps {})
+    }
+}
+
 #[function_component]
 pub fn TextInput(props: &TextInputProps) -> Html {
+    use_stylesheet();
+
     let oninput = use_callback(
         |_, (node_ref, oninput)| {
             let element = node_ref.cast::<HtmlInputElement>().unwrap();
@@ -38,24 +64,72 @@ pub fn TextInput(props: &TextInputProps) -> Html {
         (props.node_ref.clone(), props.oninput.clone()),
     );
 
-    if props.autofocus {
-        if let Some(element) = props.node_ref.cast::<HtmlInputElement>() {
-            element.focus().unwrap();
-        }
-    }
+    let onkeydown = use_callback(
+        |event: KeyboardEvent, (node_ref, keymap, oninput, onkeydown)| {
+            onkeydown.emit(event.clone());
+
+            if event.default_prevented() {
+                return;
+            }
+
+            let Some(action) = keymap.event_action(&event) else {
+                return;
+            };
+
+            let Some(element) = node_ref.cast::<HtmlInputElement>() else {
+                return;
+            };
+
+            event.prevent_default();
+
+            match action {
+                InputAction::Clear => {
+                    element.set_value("");
+                    oninput.emit(String::new());
+                }
+                InputAction::Blur => element.blur().unwrap(),
+            }
+        },
+        (
+            props.node_ref.clone(),
+            props.keymap.clone(),
+            props.oninput.clone(),
+            props.onkeydown.clone(),
+        ),
+    );
+
+    use_effect_with_deps(
+        |(node_ref, autofocus)| {
+            if *autofocus {
+                if let Some(element) = node_ref.cast::<HtmlInputElement>() {
+                    element.focus().unwrap();
+                }
+            }
+        },
+        (props.node_ref.clone(), props.autofocus),
+    );
 
     html! {
         <input
-            class={ classes!("garlic-text-input", props.class.clone()) }
+            class={ classes!(
+                "garlic-text-input",
+                props.invalid.then_some("garlic-invalid"),
+                props.class.clone(),
+            ) }
             style={ props.style.clone() }
             value={ props.value.clone() }
             title={ props.title.clone() }
             placeholder={ props.placeholder.clone() }
-            type="text"
+            type={ props.input_type.clone() }
+            inputmode={ props.inputmode.clone() }
+            min={ props.min.clone() }
+            max={ props.max.clone() }
+            step={ props.step.clone() }
+            aria-invalid={ props.invalid.then_some("true") }
             autofocus={ props.autofocus }
             oninput={ oninput }
             onkeypress={ props.onkeypress.clone() }
-            onkeydown={ props.onkeydown.clone() }
+            onkeydown={ onkeydown }
             onkeyup={ props.onkeyup.clone() }
             onfocus={ props.onfocus.clone() }
             onblur={ props.onblur.clone() }
@@ -0,0 +1,134 @@
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+
+use yew::prelude::*;
+
+use crate::Timeout;
+
+struct ThrottleState<T> {
+    timeout: Option<Timeout>,
+    pending: Option<T>,
+}
+
+/// Emits a callback at most once every `ms` milliseconds.
+///
+/// The first call is emitted right away, the last call made while throttled
+/// is emitted once `ms` milliseconds have passed. A pending call is cancelled when dropped.
+pub struct Throttle<T> {
+    ms: u32,
+    callback: Callback<T>,
+    state: Rc<RefCell<ThrottleState<T>>>,
+}
+
+impl<T: 'static> Throttle<T> {
+    pub fn new(ms: u32, callback: Callback<T>) -> Self {
+        Self {
+            ms,
+            callback,
+            state: Rc::new(RefCell::new(ThrottleState {
+                timeout: None,
+                pending: None,
+            })),
+        }
+    }
+
+    /// Emits `value` now if not throttled, otherwise once the throttle ends.
+    pub fn call(&self, value: T) {
+        let mut state = self.state.borrow_mut();
+
+        if state.timeout.is_some() {
+            state.pending = Some(value);
+            return;
+        }
+
+        state.timeout = Some(Self::throttle(
+            self.ms,
+            self.callback.clone(),
+            Rc::downgrade(&self.state),
+        ));
+
+        drop(state);
+        self.callback.emit(value);
+    }
+
+    /// Cancels the pending call if any, and ends the throttle.
+    pub fn cancel(&self) {
+        let mut state = self.state.borrow_mut();
+        state.timeout.take();
+        state.pending.take();
+    }
+
+    /// Returns true if calls are currently throttled.
+    pub fn is_throttled(&self) -> bool {
+        self.state.borrow().timeout.is_some()
+    }
+
+    fn throttle(ms: u32, callback: Callback<T>, state: Weak<RefCell<ThrottleState<T>>>) -> Timeout {
+        Timeout::new(ms, move || {
+            let Some(state) = state.upgrade() else {
+                return;
+            };
+
+            let mut borrowed = state.borrow_mut();
+
+            // a call made while throttled is emitted, and starts a new throttle
+            match borrowed.pending.take() {
+                Some(value) => {
+                    let timeout = Self::throttle(ms, callback.clone(), Rc::downgrade(&state));
+                    borrowed.timeout = Some(timeout);
+
+                    drop(borrowed);
+                    callback.emit(value);
+                }
+                None => {
+                    borrowed.timeout = None;
+                }
+            }
+        })
+    }
+}
+
+impl<T> Drop for Throttle<T> {
+    fn drop(&mut self) {
+        self.state.borrow_mut().timeout.take();
+    }
+}
+
+/// Returns a callback that emits `callback` at most once every `ms` milliseconds.
+///
+/// The latest `callback` is always emitted. A pending call is cancelled on unmount.
+// the bound is duplicated by `#[hook]`
+#[allow(clippy::multiple_bound_locations)]
+#[hook]
+pub fn use_throttle<T: 'static>(callback: Callback<T>, ms: u32) -> Callback<T> {
+    let latest = use_mut_ref(|| callback.clone());
+    *latest.borrow_mut() = callback;
+
+    let throttle = use_memo(
+        |ms| {
+            let callback = Callback::from(move |value| {
+                let callback = latest.borrow().clone();
+                callback.emit(value);
+            });
+
+            let throttle = Rc::new(Throttle::new(*ms, callback));
+
+            let call = {
+                let throttle = throttle.clone();
+                Callback::from(move |value| throttle.call(value))
+            };
+
+            (throttle, call)
+        },
+        ms,
+    );
+
+    {
+        let throttle = throttle.0.clone();
+        use_effect_with_deps(move |_| move || throttle.cancel(), ms);
+    }
+
+    throttle.1.clone()
+}
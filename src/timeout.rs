@@ -0,0 +1,59 @@
+use std::{mem, rc::Rc};
+
+use yew::prelude::*;
+
+use crate::clock;
+
+/// Calls a function once after a delay, unless dropped before then.
+#[derive(Debug, PartialEq)]
+pub struct Timeout {
+    id: i32,
+}
+
+impl Timeout {
+    pub fn new<F>(ms: u32, f: F) -> Self
+    where
+        F: FnOnce() + 'static,
+    {
+        let id = clock().set_timeout(ms, Box::new(f));
+
+        Self { id }
+    }
+
+    pub fn forget(self) {
+        mem::forget(self);
+    }
+}
+
+impl Drop for Timeout {
+    fn drop(&mut self) {
+        clock().clear_timeout(self.id);
+    }
+}
+
+/// Calls `f` once, `ms` milliseconds after the component is mounted or `ms` changes.
+///
+/// If `ms` is `None` no timeout is set. A pending call is cancelled on unmount.
+#[hook]
+pub fn use_timeout<F>(f: F, ms: Option<u32>)
+where
+    F: Fn() + 'static,
+{
+    let f = Rc::new(f) as Rc<dyn Fn()>;
+    let latest = use_mut_ref(|| f.clone());
+    *latest.borrow_mut() = f;
+
+    use_effect_with_deps(
+        move |ms| {
+            let timeout = ms.map(|ms| {
+                Timeout::new(ms, move || {
+                    let f = latest.borrow().clone();
+                    f();
+                })
+            });
+
+            move || drop(timeout)
+        },
+        ms,
+    );
+}
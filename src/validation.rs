@@ -0,0 +1,696 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    rc::Rc,
+};
+
+#[cfg(feature = "input")]
+use std::{cell::RefCell, collections::HashMap};
+
+#[cfg(feature = "input")]
+use yew::prelude::*;
+
+/// Why a value isn't valid, see [`Validator`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationError {
+    Required,
+    TooShort {
+        min: usize,
+    },
+    TooLong {
+        max: usize,
+    },
+    NotANumber,
+    BelowMin(f64),
+    AboveMax(f64),
+    /// The number isn't `base` plus a multiple of `step`.
+    StepMismatch {
+        base: f64,
+        step: f64,
+    },
+    InvalidEmail,
+    /// An error with its own message, like those of [`PatternValidator`].
+    Custom(String),
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::Required => write!(f, "required"),
+            ValidationError::TooShort { min } => write!(f, "must be at least {} characters", min),
+            ValidationError::TooLong { max } => write!(f, "must be at most {} characters", max),
+            ValidationError::NotANumber => write!(f, "not a number"),
+            ValidationError::BelowMin(min) => write!(f, "must be at least {}", min),
+            ValidationError::AboveMax(max) => write!(f, "must be at most {}", max),
+            ValidationError::StepMismatch { base, step } if *base == 0.0 => {
+                write!(f, "must be a multiple of {}", step)
+            }
+            ValidationError::StepMismatch { base, step } => {
+                write!(f, "must be {} plus a multiple of {}", base, step)
+            }
+            ValidationError::InvalidEmail => write!(f, "not an email address"),
+            ValidationError::Custom(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+/// Checks values, like the text of a form field.
+///
+/// Validators compose with [`Validator::and`], [`Validator::or`] and [`Validator::optional`],
+/// and any `Fn(&T) -> Result<(), ValidationError>` is a validator.
+pub trait Validator<T: ?Sized> {
+    fn validate(&self, value: &T) -> Result<(), ValidationError>;
+
+    /// Checks with `self` then `other`, failing with the first error.
+    #[inline]
+    fn and<V: Validator<T>>(self, other: V) -> AndValidator<Self, V>
+    where
+        Self: Sized,
+    {
+        AndValidator(self, other)
+    }
+
+    /// Checks with `self`, or `other` if `self` fails, failing with the error of `other`.
+    #[inline]
+    fn or<V: Validator<T>>(self, other: V) -> OrValidator<Self, V>
+    where
+        Self: Sized,
+    {
+        OrValidator(self, other)
+    }
+
+    /// Accepts empty values, only checking the others.
+    #[inline]
+    fn optional(self) -> OptionalValidator<Self>
+    where
+        Self: Sized,
+    {
+        OptionalValidator(self)
+    }
+}
+
+impl<T: ?Sized, F> Validator<T> for F
+where
+    F: Fn(&T) -> Result<(), ValidationError>,
+{
+    #[inline]
+    fn validate(&self, value: &T) -> Result<(), ValidationError> {
+        self(value)
+    }
+}
+
+/// See [`Validator::and`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AndValidator<A, B>(A, B);
+
+impl<T: ?Sized, A: Validator<T>, B: Validator<T>> Validator<T> for AndValidator<A, B> {
+    #[inline]
+    fn validate(&self, value: &T) -> Result<(), ValidationError> {
+        self.0.validate(value)?;
+        self.1.validate(value)
+    }
+}
+
+/// See [`Validator::or`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrValidator<A, B>(A, B);
+
+impl<T: ?Sized, A: Validator<T>, B: Validator<T>> Validator<T> for OrValidator<A, B> {
+    #[inline]
+    fn validate(&self, value: &T) -> Result<(), ValidationError> {
+        self.0.validate(value).or_else(|_| self.1.validate(value))
+    }
+}
+
+/// See [`Validator::optional`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OptionalValidator<V>(V);
+
+impl<V: Validator<str>> Validator<str> for OptionalValidator<V> {
+    #[inline]
+    fn validate(&self, value: &str) -> Result<(), ValidationError> {
+        if value.trim().is_empty() {
+            Ok(())
+        } else {
+            self.0.validate(value)
+        }
+    }
+}
+
+/// Rejects blank text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RequiredValidator;
+
+impl Validator<str> for RequiredValidator {
+    #[inline]
+    fn validate(&self, value: &str) -> Result<(), ValidationError> {
+        if value.trim().is_empty() {
+            Err(ValidationError::Required)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Checks the number of characters of a text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LengthValidator {
+    pub min: Option<usize>,
+    pub max: Option<usize>,
+}
+
+impl LengthValidator {
+    #[inline]
+    pub fn new(min: Option<usize>, max: Option<usize>) -> Self {
+        Self { min, max }
+    }
+}
+
+impl Validator<str> for LengthValidator {
+    fn validate(&self, value: &str) -> Result<(), ValidationError> {
+        let len = value.chars().count();
+
+        match (self.min, self.max) {
+            (Some(min), _) if len < min => Err(ValidationError::TooShort { min }),
+            (_, Some(max)) if len > max => Err(ValidationError::TooLong { max }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Checks that a text matches a predicate, failing with a message.
+#[derive(Clone)]
+pub struct PatternValidator {
+    matches: Rc<dyn Fn(&str) -> bool>,
+    message: String,
+}
+
+impl PatternValidator {
+    /// Creates a validator accepting texts where `matches` returns true.
+    pub fn new(matches: impl Fn(&str) -> bool + 'static, message: impl Into<String>) -> Self {
+        Self {
+            matches: Rc::new(matches),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Debug for PatternValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PatternValidator")
+            .field("message", &self.message)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Validator<str> for PatternValidator {
+    #[inline]
+    fn validate(&self, value: &str) -> Result<(), ValidationError> {
+        if (self.matches)(value) {
+            Ok(())
+        } else {
+            Err(ValidationError::Custom(self.message.clone()))
+        }
+    }
+}
+
+/// Checks that a number, or a text parsing to one, is between `min` and `max`, and on a
+/// `step` from `min`, or from `0` without a `min`, like the attributes of `<input type="number">`.
+///
+/// `NaN` and infinities aren't numbers here, even though they parse as `f64`.
+///
+/// [`NumberInput`](crate::NumberInput) checks its number with it.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RangeValidator {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub step: Option<f64>,
+}
+
+impl RangeValidator {
+    #[inline]
+    pub fn new(min: Option<f64>, max: Option<f64>) -> Self {
+        Self {
+            min,
+            max,
+            step: None,
+        }
+    }
+
+    #[inline]
+    pub fn with_step(mut self, step: f64) -> Self {
+        self.step = Some(step);
+        self
+    }
+}
+
+impl Validator<f64> for RangeValidator {
+    fn validate(&self, value: &f64) -> Result<(), ValidationError> {
+        // `NaN` compares false with both bounds, so it would pass them
+        if !value.is_finite() {
+            return Err(ValidationError::NotANumber);
+        }
+
+        match (self.min, self.max) {
+            (Some(min), _) if *value < min => return Err(ValidationError::BelowMin(min)),
+            (_, Some(max)) if *value > max => return Err(ValidationError::AboveMax(max)),
+            _ => {}
+        }
+
+        let Some(step) = self.step.filter(|step| *step > 0.0) else {
+            return Ok(());
+        };
+
+        let base = self.min.unwrap_or(0.0);
+        let steps = (value - base) / step;
+
+        // decimal steps aren't exact in binary, like `0.1 + 0.2`
+        if (steps - steps.round()).abs() > 1e-9 * steps.abs().max(1.0) {
+            return Err(ValidationError::StepMismatch { base, step });
+        }
+
+        Ok(())
+    }
+}
+
+impl Validator<str> for RangeValidator {
+    fn validate(&self, value: &str) -> Result<(), ValidationError> {
+        let number = value
+            .trim()
+            .parse::<f64>()
+            .map_err(|_| ValidationError::NotANumber)?;
+
+        self.validate(&number)
+    }
+}
+
+/// Checks that a text looks like an email address, like `name@example.com`.
+///
+/// This is the loose check of `<input type="email">`, the only way to really check an
+/// address is to send an email to it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EmailValidator;
+
+impl Validator<str> for EmailValidator {
+    fn validate(&self, value: &str) -> Result<(), ValidationError> {
+        let valid = value.split_once('@').is_some_and(|(local, domain)| {
+            !local.is_empty()
+                && !domain.is_empty()
+                && !value.chars().any(char::is_whitespace)
+                && !domain.contains('@')
+                && domain.split('.').all(|label| !label.is_empty())
+        });
+
+        if valid {
+            Ok(())
+        } else {
+            Err(ValidationError::InvalidEmail)
+        }
+    }
+}
+
+/// The fields of a [`Form`], by id.
+#[cfg(feature = "input")]
+#[derive(Debug, Default)]
+struct FormFields {
+    next_id: usize,
+    valid: HashMap<usize, bool>,
+    /// Bumped when the validity of a field changes.
+    version: u64,
+}
+
+/// The state of the closest [`Form`], see [`use_form`].
+#[cfg(feature = "input")]
+#[derive(Clone)]
+pub struct FormContext {
+    fields: Rc<RefCell<FormFields>>,
+    /// The version at the last render, so consumers rerender when validity changes.
+    version: u64,
+    submitted: bool,
+    update: UseForceUpdateHandle,
+}
+
+#[cfg(feature = "input")]
+impl FormContext {
+    /// Returns true if every field of the form is valid.
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.fields.borrow().valid.values().all(|&valid| valid)
+    }
+
+    /// Returns true once submitting has been attempted, so every field shows its errors.
+    #[inline]
+    pub fn is_submitted(&self) -> bool {
+        self.submitted
+    }
+
+    fn register(&self) -> usize {
+        let mut fields = self.fields.borrow_mut();
+
+        let id = fields.next_id;
+        fields.next_id += 1;
+
+        id
+    }
+
+    fn set_valid(&self, id: usize, valid: bool) {
+        let changed = self.fields.borrow_mut().valid.insert(id, valid) != Some(valid);
+
+        if changed {
+            self.fields.borrow_mut().version += 1;
+            self.update.force_update();
+        }
+    }
+
+    fn unregister(&self, id: usize) {
+        if self.fields.borrow_mut().valid.remove(&id).is_some() {
+            self.fields.borrow_mut().version += 1;
+            self.update.force_update();
+        }
+    }
+}
+
+#[cfg(feature = "input")]
+impl PartialEq for FormContext {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.fields, &other.fields)
+            && self.version == other.version
+            && self.submitted == other.submitted
+    }
+}
+
+/// Gets the closest [`Form`], for example to disable a submit button while it's invalid.
+#[cfg(feature = "input")]
+#[hook]
+pub fn use_form() -> Option<FormContext> {
+    use_context::<FormContext>()
+}
+
+#[cfg(feature = "input")]
+#[derive(Properties, PartialEq)]
+pub struct FormProps {
+    #[prop_or_default]
+    pub class: Classes,
+    #[prop_or_default]
+    pub style: String,
+    #[prop_or_default]
+    pub children: Children,
+    /// Called when the form is submitted with every field valid.
+    #[prop_or_default]
+    pub onsubmit: Callback<()>,
+}
+
+/// A `<form>` aggregating the validity of the [`use_field`] fields inside it.
+///
+/// Submitting is blocked while a field is invalid, and shows the errors of every field
+/// instead. The form then has the `garlic-invalid` class until every field is valid.
+#[cfg(feature = "input")]
+#[function_component]
+pub fn Form(props: &FormProps) -> Html {
+    let fields = use_mut_ref(FormFields::default);
+    let submitted = use_state_eq(|| false);
+    let update = use_force_update();
+
+    let version = fields.borrow().version;
+    let context = FormContext {
+        fields,
+        version,
+        submitted: *submitted,
+        update,
+    };
+
+    let onsubmit = use_callback(
+        |event: SubmitEvent, (context, submitted, onsubmit)| {
+            event.prevent_default();
+
+            if context.is_valid() {
+                onsubmit.emit(());
+            } else {
+                submitted.set(true);
+            }
+        },
+        (context.clone(), submitted.clone(), props.onsubmit.clone()),
+    );
+
+    let invalid = context.submitted && !context.is_valid();
+
+    html! {
+        <form
+            class={ classes!(invalid.then_some("garlic-invalid"), props.class.clone()) }
+            style={ props.style.clone() }
+            aria-invalid={ invalid.then_some("true") }
+            novalidate=true
+            onsubmit={ onsubmit }
+        >
+            <ContextProvider<FormContext> context={ context }>
+                { for props.children.iter() }
+            </ContextProvider<FormContext>>
+        </form>
+    }
+}
+
+/// The state of a form field, see [`use_field`].
+#[cfg(feature = "input")]
+#[derive(Clone, PartialEq)]
+pub struct UseFieldHandle {
+    value: UseStateHandle<String>,
+    initial: Rc<String>,
+    touched: UseStateHandle<bool>,
+    error: Option<ValidationError>,
+    submitted: bool,
+}
+
+#[cfg(feature = "input")]
+impl UseFieldHandle {
+    #[inline]
+    pub fn value(&self) -> String {
+        (*self.value).clone()
+    }
+
+    #[inline]
+    pub fn set(&self, value: String) {
+        self.value.set(value);
+    }
+
+    /// Marks the field as touched, usually when it loses the focus.
+    #[inline]
+    pub fn touch(&self) {
+        self.touched.set(true);
+    }
+
+    #[inline]
+    pub fn is_touched(&self) -> bool {
+        *self.touched
+    }
+
+    /// Returns true if the value differs from the initial one.
+    #[inline]
+    pub fn is_dirty(&self) -> bool {
+        *self.value != *self.initial
+    }
+
+    #[inline]
+    pub fn error(&self) -> Option<&ValidationError> {
+        self.error.as_ref()
+    }
+
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Returns true if the field should show its error: it's invalid, and touched or
+    /// its form was submitted.
+    ///
+    /// Pass it to [`TextInputProps::invalid`](crate::TextInputProps::invalid).
+    #[inline]
+    pub fn is_invalid(&self) -> bool {
+        !self.is_valid() && (self.is_touched() || self.submitted)
+    }
+
+    /// Restores the initial value, and forgets the field was touched.
+    #[inline]
+    pub fn reset(&self) {
+        self.value.set((*self.initial).clone());
+        self.touched.set(false);
+    }
+
+    /// Sets the value, for [`TextInputProps::oninput`](crate::TextInputProps::oninput).
+    #[inline]
+    pub fn oninput(&self) -> Callback<String> {
+        let value = self.value.clone();
+        Callback::from(move |input| value.set(input))
+    }
+
+    /// Touches the field, for [`TextInputProps::onblur`](crate::TextInputProps::onblur).
+    #[inline]
+    pub fn onblur(&self) -> Callback<FocusEvent> {
+        let touched = self.touched.clone();
+        Callback::from(move |_| touched.set(true))
+    }
+}
+
+/// Keeps the value of a form field, checked with `validator` on every render.
+///
+/// Inside a [`Form`], the field's validity counts towards the form's.
+#[cfg(feature = "input")]
+#[hook]
+pub fn use_field<V>(initial: impl Into<String>, validator: V) -> UseFieldHandle
+where
+    V: Validator<str>,
+{
+    let initial = use_memo(|_| initial.into(), ());
+    let value = {
+        let initial = initial.clone();
+        use_state_eq(move || (*initial).clone())
+    };
+    let touched = use_state_eq(|| false);
+
+    let error = validator.validate(&value).err();
+
+    let form = use_form();
+    let id = {
+        let form = form.clone();
+        use_memo(move |_| form.map(|form| (form.register(), form)), ())
+    };
+
+    {
+        let id = id.clone();
+
+        use_effect_with_deps(
+            move |valid| {
+                if let Some((id, form)) = &*id {
+                    form.set_valid(*id, *valid);
+                }
+            },
+            error.is_none(),
+        );
+    }
+
+    use_effect_with_deps(
+        move |_| {
+            move || {
+                if let Some((id, form)) = &*id {
+                    form.unregister(*id);
+                }
+            }
+        },
+        (),
+    );
+
+    UseFieldHandle {
+        value,
+        initial,
+        touched,
+        error,
+        submitted: form.is_some_and(|form| form.is_submitted()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_validators() {
+        assert_eq!(
+            RequiredValidator.validate(" "),
+            Err(ValidationError::Required)
+        );
+        assert_eq!(RequiredValidator.validate("a"), Ok(()));
+
+        let length = LengthValidator::new(Some(2), Some(3));
+        assert_eq!(
+            length.validate("é"),
+            Err(ValidationError::TooShort { min: 2 })
+        );
+        assert_eq!(length.validate("éé"), Ok(()));
+        assert_eq!(
+            length.validate("abcd"),
+            Err(ValidationError::TooLong { max: 3 })
+        );
+
+        let range = RangeValidator::new(Some(0.0), Some(10.0));
+        assert_eq!(range.validate("5"), Ok(()));
+        assert_eq!(range.validate("-1"), Err(ValidationError::BelowMin(0.0)));
+        assert_eq!(range.validate(&11.0), Err(ValidationError::AboveMax(10.0)));
+        assert_eq!(range.validate("ten"), Err(ValidationError::NotANumber));
+
+        let stepped = RangeValidator::default().with_step(0.1);
+        assert_eq!(stepped.validate("0.3"), Ok(()));
+        assert_eq!(stepped.validate("-2.7"), Ok(()));
+        assert_eq!(
+            stepped.validate("0.35"),
+            Err(ValidationError::StepMismatch {
+                base: 0.0,
+                step: 0.1
+            })
+        );
+
+        for not_finite in ["NaN", "inf", "-infinity"] {
+            assert_eq!(
+                range.validate(not_finite),
+                Err(ValidationError::NotANumber),
+                "{}",
+                not_finite
+            );
+        }
+        assert_eq!(
+            RangeValidator::default().validate(&f64::NAN),
+            Err(ValidationError::NotANumber)
+        );
+    }
+
+    #[test]
+    fn email_validator() {
+        for valid in ["name@example.com", "a.b+c@sub.example.org", "x@localhost"] {
+            assert_eq!(EmailValidator.validate(valid), Ok(()), "{}", valid);
+        }
+
+        for invalid in [
+            "",
+            "name",
+            "@example.com",
+            "name@",
+            "a@b@c",
+            "a b@c.d",
+            "a@b..c",
+        ] {
+            assert_eq!(
+                EmailValidator.validate(invalid),
+                Err(ValidationError::InvalidEmail),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn validators_compose() {
+        let email = RequiredValidator.and(EmailValidator);
+        assert_eq!(email.validate(""), Err(ValidationError::Required));
+        assert_eq!(email.validate("a"), Err(ValidationError::InvalidEmail));
+
+        let optional = EmailValidator.optional();
+        assert_eq!(optional.validate(""), Ok(()));
+        assert_eq!(optional.validate("a"), Err(ValidationError::InvalidEmail));
+
+        let code = PatternValidator::new(|v| v.starts_with('#'), "must start with #");
+        let id = RangeValidator::new(Some(1.0), None).or(code);
+        assert_eq!(id.validate("3"), Ok(()));
+        assert_eq!(id.validate("#a"), Ok(()));
+        assert_eq!(
+            id.validate("a").unwrap_err().to_string(),
+            "must start with #"
+        );
+
+        let not_admin = |value: &str| match value {
+            "admin" => Err(ValidationError::Custom(String::from("reserved"))),
+            _ => Ok(()),
+        };
+        assert!(RequiredValidator.and(not_admin).validate("admin").is_err());
+    }
+}
@@ -0,0 +1,218 @@
+#![cfg(all(feature = "sortable", feature = "input"))]
+
+use garlic::*;
+use tokio::{runtime::Builder, task::LocalSet};
+use yew::{prelude::*, LocalServerRenderer};
+
+#[derive(Properties, PartialEq)]
+struct AppProps {
+    html: Html,
+}
+
+#[function_component]
+fn App(props: &AppProps) -> Html {
+    props.html.clone()
+}
+
+/// Renders `html` to a string, like a server would.
+fn render(html: Html) -> String {
+    render_with(html, false)
+}
+
+/// Renders `html` with the markers the client needs to hydrate it, when `hydratable`.
+fn render_with(html: Html, hydratable: bool) -> String {
+    let renderer = LocalServerRenderer::<App>::with_props(AppProps { html }).hydratable(hydratable);
+
+    let runtime = Builder::new_current_thread().build().unwrap();
+    runtime.block_on(LocalSet::new().run_until(renderer.render()))
+}
+
+#[test]
+fn sortable_list_renders_items_in_source_order() {
+    let html = render(html! {
+        <SortableList>
+            <span>{ "a" }</span>
+            <span>{ "b" }</span>
+            <span>{ "c" }</span>
+        </SortableList>
+    });
+
+    let a = html.find(">a<").unwrap();
+    let b = html.find(">b<").unwrap();
+    let c = html.find(">c<").unwrap();
+    assert!(a < b && b < c, "{}", html);
+
+    assert!(html.contains("garlic-sortable-list"), "{}", html);
+    assert!(html.contains("garlic-list-item"), "{}", html);
+
+    // items aren't measured yet, so they're laid out by the browser
+    assert!(!html.contains("position:absolute"), "{}", html);
+    assert!(!html.contains("width:0px"), "{}", html);
+}
+
+#[test]
+fn hydratable_render_has_component_markers() {
+    let html = html! {
+        <SortableList>
+            <span>{ "a" }</span>
+            <span>{ "b" }</span>
+        </SortableList>
+    };
+
+    let hydratable = render_with(html.clone(), true);
+    assert!(hydratable.contains("<!--<[garlic::"), "{}", hydratable);
+
+    // without the markers it's the same markup
+    let stripped = strip_markers(&hydratable);
+    assert_eq!(stripped, render_with(html, false));
+}
+
+/// Removes the `<!--...-->` hydration markers.
+fn strip_markers(html: &str) -> String {
+    let mut stripped = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<!--") {
+        stripped.push_str(&rest[..start]);
+
+        let end = rest[start..].find("-->").unwrap();
+        rest = &rest[start + end + 3..];
+    }
+
+    stripped.push_str(rest);
+    stripped
+}
+
+#[test]
+fn sortable_table_renders_rows() {
+    let html = render(html! {
+        <table>
+            <SortableTable>
+                <SortableRow><td>{ "a" }</td></SortableRow>
+                <SortableRow><td>{ "b" }</td></SortableRow>
+            </SortableTable>
+        </table>
+    });
+
+    assert!(html.contains("class=\"garlic-sortable-table\""), "{}", html);
+    assert_eq!(html.matches("garlic-sortable-row").count(), 2, "{}", html);
+    assert!(
+        html.find(">a<").unwrap() < html.find(">b<").unwrap(),
+        "{}",
+        html
+    );
+}
+
+#[test]
+fn drag_components_render() {
+    let html = render(html! {
+        <DragArea>
+            <Draggable>{ "drag" }</Draggable>
+            <Droppable>{ "drop" }</Droppable>
+        </DragArea>
+    });
+
+    assert!(html.contains("garlic-drag-area"), "{}", html);
+    assert!(html.contains("garlic-draggable"), "{}", html);
+    assert!(!html.contains("garlic-dragging"), "{}", html);
+    assert!(html.contains("garlic-droppable"), "{}", html);
+}
+
+#[test]
+fn spacer_renders_size() {
+    let html = render(html! {
+        <Spacer size={ 20.0 } direction={ Direction::Column } smooth=true/>
+    });
+
+    assert!(html.contains("height:20px;"), "{}", html);
+}
+
+#[test]
+fn inputs_render() {
+    let html = render(html! {
+        <>
+            <TextInput value="text" autofocus=true/>
+            <SearchQuery/>
+            <SearchQuery history_key="search" suggestions={ vec![String::from("open")] }/>
+            <SearchQuery debounce_ms={ 300 } loading=true/>
+        </>
+    });
+
+    assert!(html.contains("garlic-text-input"), "{}", html);
+    assert!(html.contains("value=\"text\""), "{}", html);
+    assert!(html.contains("garlic-search-query"), "{}", html);
+    assert!(html.contains("placeholder=\"Search\""), "{}", html);
+    // the suggestions are closed until focused
+    assert!(html.contains("class=\"garlic-search\""), "{}", html);
+    assert!(!html.contains("garlic-search-suggestions"), "{}", html);
+    assert!(html.contains("garlic-loading"), "{}", html);
+}
+
+#[test]
+fn parsed_inputs_render() {
+    let html = render(html! {
+        <>
+            <NumberInput value={ 2.5 } min={ 0.0 } step={ 0.5 }/>
+            <ParsedInput<u32>
+                value={ 7 }
+                input={ TextInputProps {
+                    placeholder: Some(String::from("Count")),
+                    ..Default::default()
+                } }
+            />
+            <PasswordInput value="secret"/>
+            <EmailInput/>
+        </>
+    });
+
+    assert!(html.contains("type=\"number\""), "{}", html);
+    assert!(html.contains("min=\"0\""), "{}", html);
+    assert!(html.contains("step=\"0.5\""), "{}", html);
+    assert!(html.contains("value=\"2.5\""), "{}", html);
+    assert!(html.contains("value=\"7\""), "{}", html);
+    assert!(html.contains("garlic-parsed-input"), "{}", html);
+    assert!(html.contains("placeholder=\"Count\""), "{}", html);
+    assert!(html.contains("type=\"password\""), "{}", html);
+    assert!(html.contains("type=\"email\""), "{}", html);
+    assert!(!html.contains("garlic-invalid"), "{}", html);
+}
+
+#[function_component]
+fn EmailField() -> Html {
+    let email = use_field("not an email", RequiredValidator.and(EmailValidator));
+
+    html! {
+        <TextInput
+            value={ email.value() }
+            invalid={ email.is_invalid() }
+            oninput={ email.oninput() }
+            onblur={ email.onblur() }
+        />
+    }
+}
+
+#[test]
+fn form_renders_fields() {
+    let html = render(html! {
+        <Form>
+            <EmailField/>
+        </Form>
+    });
+
+    assert!(html.starts_with("<form"), "{}", html);
+    assert!(html.contains("novalidate"), "{}", html);
+    assert!(html.contains("value=\"not an email\""), "{}", html);
+    // errors only show once the field is touched or the form submitted
+    assert!(!html.contains("garlic-invalid"), "{}", html);
+}
+
+#[test]
+fn detect_resize_renders_nothing() {
+    let html = render(html! {
+        <div>
+            <DetectResize node_ref={ NodeRef::default() }/>
+        </div>
+    });
+
+    assert_eq!(html, "<div></div>");
+}